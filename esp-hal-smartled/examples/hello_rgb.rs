@@ -29,7 +29,7 @@
 
 use esp_backtrace as _;
 use esp_hal::{delay::Delay, main, rmt::Rmt, time::Rate};
-use esp_hal_smartled::{smart_led_buffer, SmartLedsAdapter};
+use esp_hal_smartled::{smart_led_buffer, AdapterConfig, ClockSource, SmartLedsAdapter};
 use smart_leds::{
     brightness, gamma,
     hsv::{hsv2rgb, Hsv},
@@ -43,39 +43,40 @@ fn main() -> ! {
 
     // Configure RMT (Remote Control Transceiver) peripheral globally
     // <https://docs.espressif.com/projects/esp-idf/en/stable/esp32/api-reference/peripherals/rmt.html>
-    let rmt: Rmt<'_, esp_hal::Blocking> = {
-        let frequency: Rate = {
-            cfg_if::cfg_if! {
-                if #[cfg(feature = "esp32h2")] {
-                    Rate::from_mhz(32)
-                } else {
-                    Rate::from_mhz(80)
-                }
+    let frequency: Rate = {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "esp32h2")] {
+                Rate::from_mhz(32)
+            } else {
+                Rate::from_mhz(80)
             }
-        };
-        Rmt::new(p.RMT, frequency)
-    }
-    .expect("Failed to initialize RMT");
+        }
+    };
+    let rmt: Rmt<'_, esp_hal::Blocking> =
+        Rmt::new(p.RMT, frequency).expect("Failed to initialize RMT");
 
     // We use one of the RMT channels to instantiate a `SmartLedsAdapter` which can
     // be used directly with all `smart_led` implementations
     let rmt_channel = rmt.channel0;
     let rmt_buffer = smart_led_buffer!(1);
 
+    // The pulse widths are counted at the rate the RMT peripheral runs at
+    let config = AdapterConfig::default().with_clock_source(ClockSource::Explicit(frequency));
+
     // Each devkit uses a unique GPIO for the RGB LED, so in order to support
     // all chips we must unfortunately use `#[cfg]`s:
     let mut led: SmartLedsAdapter<_, 25> = {
         cfg_if::cfg_if! {
             if #[cfg(feature = "esp32")] {
-                SmartLedsAdapter::new(rmt_channel, p.GPIO33, rmt_buffer)
+                SmartLedsAdapter::new_with_config(rmt_channel, p.GPIO33, rmt_buffer, config)
             } else if #[cfg(feature = "esp32c3")] {
-                SmartLedsAdapter::new(rmt_channel, p.GPIO2, rmt_buffer)
+                SmartLedsAdapter::new_with_config(rmt_channel, p.GPIO2, rmt_buffer, config)
             } else if #[cfg(any(feature = "esp32c6", feature = "esp32h2"))] {
-                SmartLedsAdapter::new(rmt_channel, p.GPIO8, rmt_buffer)
+                SmartLedsAdapter::new_with_config(rmt_channel, p.GPIO8, rmt_buffer, config)
             } else if #[cfg(feature = "esp32s2")] {
-                SmartLedsAdapter::new(rmt_channel, p.GPIO18, rmt_buffer)
+                SmartLedsAdapter::new_with_config(rmt_channel, p.GPIO18, rmt_buffer, config)
             } else if #[cfg(feature = "esp32s3")] {
-                SmartLedsAdapter::new(rmt_channel, p.GPIO48, rmt_buffer)
+                SmartLedsAdapter::new_with_config(rmt_channel, p.GPIO48, rmt_buffer, config)
             }
         }
     };
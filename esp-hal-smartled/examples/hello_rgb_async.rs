@@ -26,7 +26,7 @@ use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
 use esp_backtrace as _;
 use esp_hal::{rmt::Rmt, time::Rate, timer::timg::TimerGroup, Config};
-use esp_hal_smartled::{buffer_size_async, SmartLedsAdapterAsync};
+use esp_hal_smartled::{buffer_size_async, AdapterConfig, ClockSource, SmartLedsAdapterAsync};
 use smart_leds::{
     brightness, gamma,
     hsv::{hsv2rgb, Hsv},
@@ -42,40 +42,41 @@ async fn main(_spawner: Spawner) -> ! {
 
     // Configure RMT (Remote Control Transceiver) peripheral globally
     // <https://docs.espressif.com/projects/esp-idf/en/stable/esp32/api-reference/peripherals/rmt.html>
-    let rmt: Rmt<'_, esp_hal::Async> = {
-        let frequency: Rate = {
-            cfg_if::cfg_if! {
-                if #[cfg(feature = "esp32h2")] {
-                    Rate::from_mhz(32)
-                } else {
-                    Rate::from_mhz(80)
-                }
+    let frequency: Rate = {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "esp32h2")] {
+                Rate::from_mhz(32)
+            } else {
+                Rate::from_mhz(80)
             }
-        };
-        Rmt::new(p.RMT, frequency)
-    }
-    .expect("Failed to initialize RMT")
-    .into_async();
+        }
+    };
+    let rmt: Rmt<'_, esp_hal::Async> = Rmt::new(p.RMT, frequency)
+        .expect("Failed to initialize RMT")
+        .into_async();
 
     // We use one of the RMT channels to instantiate a `SmartLedsAdapterAsync` which can
     // be used directly with all `smart_led` implementations
     let rmt_channel = rmt.channel0;
     let rmt_buffer = [0_u32; buffer_size_async(1)];
 
+    // The pulse widths are counted at the rate the RMT peripheral runs at
+    let config = AdapterConfig::default().with_clock_source(ClockSource::Explicit(frequency));
+
     // Each devkit uses a unique GPIO for the RGB LED, so in order to support
     // all chips we must unfortunately use `#[cfg]`s:
     let mut led: SmartLedsAdapterAsync<_, 25> = {
         cfg_if::cfg_if! {
             if #[cfg(feature = "esp32")] {
-                SmartLedsAdapterAsync::new(rmt_channel, p.GPIO33, rmt_buffer)
+                SmartLedsAdapterAsync::new_with_config(rmt_channel, p.GPIO33, rmt_buffer, config)
             } else if #[cfg(feature = "esp32c3")] {
-                SmartLedsAdapterAsync::new(rmt_channel, p.GPIO2, rmt_buffer)
+                SmartLedsAdapterAsync::new_with_config(rmt_channel, p.GPIO2, rmt_buffer, config)
             } else if #[cfg(any(feature = "esp32c6", feature = "esp32h2"))] {
-                SmartLedsAdapterAsync::new(rmt_channel, p.GPIO8, rmt_buffer)
+                SmartLedsAdapterAsync::new_with_config(rmt_channel, p.GPIO8, rmt_buffer, config)
             } else if #[cfg(feature = "esp32s2")] {
-                SmartLedsAdapterAsync::new(rmt_channel, p.GPIO18, rmt_buffer)
+                SmartLedsAdapterAsync::new_with_config(rmt_channel, p.GPIO18, rmt_buffer, config)
             } else if #[cfg(feature = "esp32s3")] {
-                SmartLedsAdapterAsync::new(rmt_channel, p.GPIO48, rmt_buffer)
+                SmartLedsAdapterAsync::new_with_config(rmt_channel, p.GPIO48, rmt_buffer, config)
             }
         }
     };
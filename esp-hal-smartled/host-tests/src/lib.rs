@@ -0,0 +1,16 @@
+//! Runs the tests of the parts of `esp-hal-smartled` that don't depend on
+//! esp-hal on the host:
+//!
+//! ```sh
+//! cargo test -p esp-hal-smartled-host-tests --target x86_64-unknown-linux-gnu
+//! ```
+//!
+//! The crate itself only builds for the chips, so the sources are included
+//! here directly.
+
+#![cfg_attr(not(test), no_std)]
+
+// Only the tests use the items of the crate
+#[allow(dead_code)]
+#[path = "../../src/pulses.rs"]
+mod pulses;
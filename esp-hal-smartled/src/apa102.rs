@@ -0,0 +1,89 @@
+//! Support for clocked APA102 and SK9822 LEDs, driven by an SPI peripheral
+//! instead of the RMT.
+
+use esp_hal::{spi::master::Spi, Blocking};
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+use crate::LedAdapterError;
+
+// Maximum value of the 5-bit global brightness field.
+const MAX_BRIGHTNESS: u8 = 0x1f;
+
+/// Adapter taking a blocking SPI driver and providing APA102/SK9822 LED
+/// interaction functionality using the `smart-leds` crate
+///
+/// Connect the LED clock line to SCK and the data line to MOSI of the SPI
+/// driver.
+pub struct Apa102Adapter<'d> {
+    spi: Spi<'d, Blocking>,
+    brightness: u8,
+}
+
+impl<'d> Apa102Adapter<'d> {
+    /// Create a new adapter object that drives the LEDs using the SPI driver,
+    /// with the global brightness set to its maximum.
+    pub fn new(spi: Spi<'d, Blocking>) -> Self {
+        Self {
+            spi,
+            brightness: MAX_BRIGHTNESS,
+        }
+    }
+
+    /// Set the 5-bit global brightness sent with every LED. Values above 31
+    /// are clamped.
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.set_brightness(brightness);
+        self
+    }
+
+    /// Set the 5-bit global brightness sent with every LED. Values above 31
+    /// are clamped.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness.min(MAX_BRIGHTNESS);
+    }
+
+    /// The 5-bit global brightness sent with every LED.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Return the underlying SPI driver.
+    pub fn into_inner(self) -> Spi<'d, Blocking> {
+        self.spi
+    }
+}
+
+impl SmartLedsWrite for Apa102Adapter<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    /// Send the start frame, one frame per item of the iterator and the end
+    /// frame over SPI.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        // Start frame
+        self.spi.write(&[0x00; 4])?;
+
+        let mut led_count: usize = 0;
+        for item in iterator {
+            let color = item.into();
+            self.spi
+                .write(&[0xe0 | self.brightness, color.b, color.g, color.r])?;
+            led_count += 1;
+        }
+
+        // The SK9822 latches the data on a reset frame of 32 zero bits, while
+        // the APA102 needs at least half a clock edge per LED to shift the
+        // data through the whole strip. Zeros satisfy both without lighting
+        // any LED past the end of the strip.
+        self.spi.write(&[0x00; 4])?;
+        for _ in 0..led_count.div_ceil(16) {
+            self.spi.write(&[0x00])?;
+        }
+
+        Ok(())
+    }
+}
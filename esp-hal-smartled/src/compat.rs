@@ -0,0 +1,55 @@
+//! Implementations of the `SmartLedsWrite` trait of smart-leds-trait 0.2,
+//! used by smart-leds 0.3, for projects whose other drivers still depend on
+//! it.
+
+use esp_hal::rmt::{RawChannelAccess, TxChannelInternal};
+use smart_leds_trait::SmartLedsWrite;
+
+use crate::{
+    Apa102Adapter, Hd108Adapter, Lpd8806Adapter, P9813Adapter, SmartLedsAdapter,
+    SmartLedsAdapterQueued, SmartLedsAdapterRgbw, SmartLedsAdapterSlice, SmartLedsAdapterStreaming,
+    Tm1814Adapter,
+};
+
+/// Implements the older trait by forwarding to the current one.
+macro_rules! impl_smart_leds_write_0_2 {
+    ($([$($generics:tt)*] $ty:ty $(where [$($bounds:tt)*])?),+ $(,)?) => {
+        $(
+            impl<$($generics)*> smart_leds_trait_0_2::SmartLedsWrite for $ty
+            $(where $($bounds)*)?
+            {
+                type Error = <Self as SmartLedsWrite>::Error;
+                type Color = <Self as SmartLedsWrite>::Color;
+
+                fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+                where
+                    T: IntoIterator<Item = I>,
+                    I: Into<Self::Color>,
+                {
+                    SmartLedsWrite::write(self, iterator)
+                }
+            }
+        )+
+    };
+}
+
+impl_smart_leds_write_0_2!(
+    [TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize]
+    SmartLedsAdapter<TX, BUFFER_SIZE, BITS_PER_CHANNEL>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static],
+    [TX, B] SmartLedsAdapterSlice<TX, B>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static, B: AsRef<[u32]> + AsMut<[u32]>],
+    [TX, const BUFFER_SIZE: usize, const FRAMES: usize]
+    SmartLedsAdapterQueued<TX, BUFFER_SIZE, FRAMES>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static],
+    [TX] SmartLedsAdapterStreaming<TX>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static],
+    [TX, const BUFFER_SIZE: usize] SmartLedsAdapterRgbw<TX, BUFFER_SIZE>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static],
+    [TX, const BUFFER_SIZE: usize] Tm1814Adapter<TX, BUFFER_SIZE>
+    where [TX: RawChannelAccess + TxChannelInternal + 'static],
+    ['d] Apa102Adapter<'d>,
+    ['d] Hd108Adapter<'d>,
+    ['d] Lpd8806Adapter<'d>,
+    ['d] P9813Adapter<'d>,
+);
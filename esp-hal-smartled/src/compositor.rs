@@ -0,0 +1,138 @@
+//! Blending of several layers of LED colors into one frame.
+
+use smart_leds_trait::RGB8;
+
+/// A layer of `N` colors, each with an alpha value from 0 (transparent) to
+/// 255 (opaque).
+pub struct Layer<const N: usize> {
+    pixels: [RGB8; N],
+    alpha: [u8; N],
+}
+
+impl<const N: usize> Layer<N> {
+    /// Create a fully transparent layer.
+    pub const fn new() -> Self {
+        Self {
+            pixels: [RGB8 { r: 0, g: 0, b: 0 }; N],
+            alpha: [0; N],
+        }
+    }
+
+    /// Set the color and alpha value of one LED.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn set(&mut self, index: usize, color: RGB8, alpha: u8) {
+        self.pixels[index] = color;
+        self.alpha[index] = alpha;
+    }
+
+    /// Set all LEDs to the same color and alpha value.
+    pub fn fill(&mut self, color: RGB8, alpha: u8) {
+        self.pixels = [color; N];
+        self.alpha = [alpha; N];
+    }
+
+    /// Make the whole layer transparent.
+    pub fn clear(&mut self) {
+        self.alpha = [0; N];
+    }
+
+    /// The colors of the layer.
+    pub fn pixels(&self) -> &[RGB8; N] {
+        &self.pixels
+    }
+
+    /// Mutable access to the colors of the layer.
+    pub fn pixels_mut(&mut self) -> &mut [RGB8; N] {
+        &mut self.pixels
+    }
+
+    /// The alpha values of the layer.
+    pub fn alpha(&self) -> &[u8; N] {
+        &self.alpha
+    }
+
+    /// Mutable access to the alpha values of the layer.
+    pub fn alpha_mut(&mut self) -> &mut [u8; N] {
+        &mut self.alpha
+    }
+}
+
+impl<const N: usize> Default for Layer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A stack of `LAYERS` layers of `N` LEDs, e.g. a background animation, an
+/// overlay and status indicators, blended into the colors sent to the LEDs.
+///
+/// Layers are blended from the first to the last on top of black, each
+/// according to its per-LED alpha values.
+///
+/// ```rust,ignore
+/// let mut compositor = Compositor::<64, 2>::new();
+/// compositor.layer_mut(0).fill(BLUE, 255);
+/// compositor.layer_mut(1).set(0, RED, 128);
+/// led.write(compositor.colors()).unwrap();
+/// ```
+pub struct Compositor<const N: usize, const LAYERS: usize> {
+    layers: [Layer<N>; LAYERS],
+}
+
+impl<const N: usize, const LAYERS: usize> Compositor<N, LAYERS> {
+    /// Create a compositor with all layers transparent.
+    pub const fn new() -> Self {
+        Self {
+            layers: [const { Layer::new() }; LAYERS],
+        }
+    }
+
+    /// The layer at `index`, with 0 being the bottom one.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn layer(&self, index: usize) -> &Layer<N> {
+        &self.layers[index]
+    }
+
+    /// Mutable access to the layer at `index`, with 0 being the bottom one.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn layer_mut(&mut self, index: usize) -> &mut Layer<N> {
+        &mut self.layers[index]
+    }
+
+    /// The blended colors of all layers, to be written to an adapter.
+    pub fn colors(&self) -> impl Iterator<Item = RGB8> + '_ {
+        (0..N).map(|index| {
+            self.layers.iter().fold(RGB8::default(), |below, layer| {
+                blend(below, layer.pixels[index], layer.alpha[index])
+            })
+        })
+    }
+}
+
+impl<const N: usize, const LAYERS: usize> Default for Compositor<N, LAYERS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Blends `above` on top of `below` with the given alpha value.
+fn blend(below: RGB8, above: RGB8, alpha: u8) -> RGB8 {
+    let alpha = alpha as u16;
+    let mix = |below: u8, above: u8| {
+        ((above as u16 * alpha + below as u16 * (255 - alpha) + 127) / 255) as u8
+    };
+    RGB8 {
+        r: mix(below.r, above.r),
+        g: mix(below.g, above.g),
+        b: mix(below.b, above.b),
+    }
+}
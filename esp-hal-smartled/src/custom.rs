@@ -0,0 +1,183 @@
+//! Adapter driving chips of any protocol through an [LedEncoder].
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{Channel, RawChannelAccess, TxChannelCreator, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::SmartLedsWrite;
+
+use crate::{
+    configure_channel, encode_frame,
+    encoder::{BitPulses, LedEncoder},
+    led_pulses_for_clock, reset_pulse_for_clock, AdapterConfig, ChannelState, LedAdapterError,
+    PowerScale,
+};
+
+/// Function to calculate the required RMT buffer size for a given number of
+/// LEDs driven by a [SmartLedsAdapterCustom] with the encoder `E`.
+pub const fn buffer_size_custom<E: LedEncoder>(num_leds: usize) -> usize {
+    // 1 additional pulse for the end delimiter
+    num_leds * E::PULSES_PER_LED + 1
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing LED
+/// interaction functionality using the `smart-leds` crate, with the colors
+/// converted by the [LedEncoder] `E`.
+///
+/// The encoder only has to turn the color of one LED into pulses, the adapter
+/// takes care of the timing, the end delimiter, the frame gap and the
+/// transmission like the built-in adapters.
+///
+/// ```rust,ignore
+/// let mut led = SmartLedsAdapterCustom::new(
+///     rmt.channel0,
+///     p.GPIO2,
+///     [0; buffer_size_custom::<Rgb12>(NUM_LEDS)],
+///     Rgb12,
+///     AdapterConfig::default(),
+/// );
+/// ```
+pub struct SmartLedsAdapterCustom<TX, E, const BUFFER_SIZE: usize>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    channel: ChannelState<TX>,
+    rmt_buffer: [u32; BUFFER_SIZE],
+    encoder: E,
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+}
+
+impl<'d, TX, E, const BUFFER_SIZE: usize> SmartLedsAdapterCustom<TX, E, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    /// Number of LEDs the buffer can hold.
+    const CAPACITY: usize = BUFFER_SIZE.saturating_sub(1) / E::PULSES_PER_LED;
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the encoder and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterCustom::try_new].
+    pub fn new<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new(channel, pin, rmt_buffer, encoder, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the encoder and the given configuration, returning an error instead of
+    /// panicking if the channel can't be configured.
+    pub fn try_new<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(E::PULSES_PER_LED > 0, "PULSES_PER_LED must not be 0");
+            assert!(
+                BUFFER_SIZE % E::PULSES_PER_LED == 1 % E::PULSES_PER_LED && BUFFER_SIZE > 0,
+                "BUFFER_SIZE must be sized with `buffer_size_custom`"
+            );
+        };
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            channel: ChannelState::Ready(channel),
+            rmt_buffer,
+            encoder,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            last_frame_end: None,
+            power: PowerScale::NONE,
+        })
+    }
+
+    /// The encoder of the adapter.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Mutable access to the encoder, e.g. to change its settings.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Number of LEDs the buffer can hold.
+    pub const fn capacity_leds(&self) -> usize {
+        Self::CAPACITY
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+}
+
+impl<TX, E, const BUFFER_SIZE: usize> SmartLedsWrite for SmartLedsAdapterCustom<TX, E, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    type Error = LedAdapterError;
+    type Color = E::Color;
+
+    /// Convert all items of the iterator with the encoder and add them to
+    /// the internal buffer, then start a singular RMT operation based on that
+    /// buffer.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let led_count = encode_frame(
+            &mut self.encoder,
+            &mut self.rmt_buffer,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )?;
+
+        let used = led_count * E::PULSES_PER_LED + 1;
+        self.channel.send_frame(
+            &self.rmt_buffer[..used],
+            &self.config,
+            None,
+            &mut self.last_frame_end,
+        )
+    }
+}
@@ -0,0 +1,111 @@
+//! Temporal dithering of high precision colors to 8 bits per channel.
+
+use smart_leds_trait::RGB16;
+
+use crate::{
+    encoder::{BitPulses, LedEncoder},
+    ordered_channels_fine, AdapterConfig,
+};
+
+/// Encoder of colors with 16 bits per channel for LEDs with 8 bits per
+/// channel, simulating the higher color depth by alternating the output values
+/// across frames.
+///
+/// The gamma correction, brightness and correction of the configuration are
+/// applied at full precision first, and the part of every channel lost by the
+/// conversion to 8 bits is kept per LED and added to the same LED in the next
+/// frame, so on average the LEDs show the precise color. This hides the
+/// visible 8-bit steps during slow fades and at low brightness, as long as
+/// the frames are sent at a high enough rate.
+///
+/// `N_LEDS` is the number of LEDs of the strip, each needing 3 bytes. LEDs
+/// beyond it are rounded down without dithering.
+///
+/// It is also a [ColorStage](crate::ColorStage), to dither after custom
+/// stages of a [ColorPipeline](crate::ColorPipeline).
+///
+/// ```rust,ignore
+/// let mut led = SmartLedsAdapterCustom::new(
+///     rmt.channel0,
+///     p.GPIO2,
+///     [0; buffer_size_custom::<TemporalDither<NUM_LEDS>>(NUM_LEDS)],
+///     TemporalDither::<NUM_LEDS>::new(),
+///     AdapterConfig::default().with_brightness(16),
+/// );
+/// led.write(frame.iter().copied()).unwrap();
+/// ```
+pub struct TemporalDither<const N_LEDS: usize> {
+    residuals: [[u8; 3]; N_LEDS],
+    // Index of the next LED of the frame being encoded.
+    index: usize,
+}
+
+impl<const N_LEDS: usize> TemporalDither<N_LEDS> {
+    /// Create a dither without any residuals.
+    pub const fn new() -> Self {
+        Self {
+            residuals: [[0; 3]; N_LEDS],
+            index: 0,
+        }
+    }
+
+    /// Forget the residuals, e.g. after switching to unrelated content.
+    pub fn reset(&mut self) {
+        self.residuals = [[0; 3]; N_LEDS];
+    }
+
+    /// Converts the channels of the LED at `index` from 8.8 fixed point to 8
+    /// bits, LEDs beyond `N_LEDS` are rounded down.
+    pub(crate) fn dither(&mut self, index: usize, channels: [u16; 3]) -> [u8; 3] {
+        let mut unused = [0; 3];
+        let residuals = self.residuals.get_mut(index).unwrap_or(&mut unused);
+        core::array::from_fn(|channel| dither_channel(channels[channel], &mut residuals[channel]))
+    }
+}
+
+impl<const N_LEDS: usize> Default for TemporalDither<N_LEDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N_LEDS: usize> LedEncoder for TemporalDither<N_LEDS> {
+    type Color = RGB16;
+    const PULSES_PER_LED: usize = 24;
+
+    fn start_frame(&mut self) {
+        self.index = 0;
+    }
+
+    fn encode(
+        &mut self,
+        color: RGB16,
+        pulses: &BitPulses,
+        config: &AdapterConfig,
+        out: &mut [u32],
+    ) {
+        let mut channels = self.dither(self.index, ordered_channels_fine(color, config));
+        self.index += 1;
+
+        if config.clamp_first_channel {
+            channels[0] = channels[0].min(0xfe);
+        }
+        pulses.write_channels::<8>(channels, out);
+    }
+
+    fn load(&self, color: &RGB16, config: &AdapterConfig) -> u32 {
+        ordered_channels_fine(*color, config)
+            .into_iter()
+            .map(|channel| (channel as u32 + 0x80) >> 8)
+            .sum()
+    }
+}
+
+// Adds the residual of the last frame to the value in 8.8 fixed point, and
+// keeps the part that doesn't fit into 8 bits for the next one.
+fn dither_channel(value: u16, residual: &mut u8) -> u8 {
+    let value = value as u32 + *residual as u32;
+    let output = (value >> 8).min(255);
+    *residual = (value - (output << 8)).min(255) as u8;
+    output as u8
+}
@@ -0,0 +1,111 @@
+//! Frames that are encoded once and transmitted repeatedly.
+
+use esp_hal::rmt::{RawChannelAccess, TxChannelInternal};
+use smart_leds_trait::RGB8;
+
+use crate::{
+    encode_frame, encoder::Rgb8Encoder, LedAdapterError, PowerScale, SmartLedsAdapter,
+    RMT_RAM_ONE_LED,
+};
+
+/// A frame of RGB LEDs already converted to RMT pulse codes.
+///
+/// Encode it once with [SmartLedsAdapter::encode_into] and send it as often
+/// as needed with [SmartLedsAdapter::write_encoded], without converting the
+/// colors again. The buffer has to be sized with [crate::buffer_size].
+///
+/// The pulses depend on the configuration of the adapter that encoded the
+/// frame, so frames have to be encoded again after changing it, e.g. with
+/// [SmartLedsAdapter::set_chipset].
+pub struct EncodedFrame<const BUFFER_SIZE: usize> {
+    pulses: [u32; BUFFER_SIZE],
+    led_count: usize,
+}
+
+impl<const BUFFER_SIZE: usize> EncodedFrame<BUFFER_SIZE> {
+    /// Create an empty frame.
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                BUFFER_SIZE % RMT_RAM_ONE_LED == 1,
+                "BUFFER_SIZE must be sized with `buffer_size`"
+            )
+        };
+
+        Self {
+            pulses: [0; BUFFER_SIZE],
+            led_count: 0,
+        }
+    }
+
+    /// Number of LEDs in the frame.
+    pub fn led_count(&self) -> usize {
+        self.led_count
+    }
+
+    /// The encoded pulse codes, including the end delimiter.
+    pub fn pulses(&self) -> &[u32] {
+        &self.pulses[..self.led_count * RMT_RAM_ONE_LED + 1]
+    }
+}
+
+impl<const BUFFER_SIZE: usize> Default for EncodedFrame<BUFFER_SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<TX, const BUFFER_SIZE: usize> SmartLedsAdapter<TX, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Encode the colors of the iterator into `frame` using the configuration
+    /// of this adapter, without transmitting them.
+    ///
+    /// If the colors don't fit into the frame, it is left empty. The
+    /// iterator is cloned to apply [AdapterConfig::with_power_limit](crate::AdapterConfig::with_power_limit) before
+    /// encoding.
+    pub fn encode_into<T, I, const FRAME_SIZE: usize>(
+        &self,
+        frame: &mut EncodedFrame<FRAME_SIZE>,
+        iterator: T,
+    ) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        T::IntoIter: Clone,
+        I: Into<RGB8>,
+    {
+        let iterator = iterator.into_iter();
+        let mut power =
+            PowerScale::of_colors(&Rgb8Encoder, iterator.clone().map(Into::into), &self.config);
+        let result = encode_frame(
+            &mut Rgb8Encoder,
+            &mut frame.pulses,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut power,
+            self.reset_pulse,
+        );
+
+        match result {
+            Ok(led_count) => {
+                frame.led_count = led_count;
+                Ok(())
+            }
+            Err(e) => {
+                frame.led_count = 0;
+                frame.pulses[0] = self.reset_pulse;
+                Err(e)
+            }
+        }
+    }
+
+    /// Transmit a frame encoded with [SmartLedsAdapter::encode_into].
+    pub fn write_encoded<const FRAME_SIZE: usize>(
+        &mut self,
+        frame: &EncodedFrame<FRAME_SIZE>,
+    ) -> Result<(), LedAdapterError> {
+        self.write_raw(frame.pulses())
+    }
+}
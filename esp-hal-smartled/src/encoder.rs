@@ -0,0 +1,208 @@
+//! Conversion of colors to RMT pulse codes, independent of any RMT channel.
+//!
+//! The adapters encode and transmit in one go. The [Encoder] only does the
+//! first part, e.g. to encode frames on one core and transmit them with
+//! [SmartLedsAdapter::write_raw](crate::SmartLedsAdapter::write_raw) on
+//! another.
+//!
+//! The [LedEncoder] trait describes how a color of a chip is turned into
+//! pulses, so chips the crate doesn't know can be driven by a
+//! [SmartLedsAdapterCustom](crate::SmartLedsAdapterCustom).
+
+use smart_leds_trait::{RGB8, RGBW};
+
+pub use crate::pulses::BitPulses;
+use crate::{
+    correct_channel, encode_frame, led_pulses_for_clock, pulses, reset_pulse_for_clock, rgb_load,
+    AdapterConfig, LedAdapterError, LedTiming, PowerScale, RgbBitsEncoder, RMT_RAM_ONE_LED,
+};
+
+/// Conversion of the color of one LED to RMT pulse codes.
+///
+/// ```rust,ignore
+/// // A chip expecting 12 bits per channel
+/// struct Rgb12;
+///
+/// impl LedEncoder for Rgb12 {
+///     type Color = RGB16;
+///     const PULSES_PER_LED: usize = 36;
+///
+///     fn encode(&mut self, color: RGB16, pulses: &BitPulses, _: &AdapterConfig, out: &mut [u32]) {
+///         for (channel, out) in [color.r, color.g, color.b].into_iter().zip(out.chunks_exact_mut(12)) {
+///             pulses.write_bits((channel >> 4) as u32, out);
+///         }
+///     }
+/// }
+/// ```
+pub trait LedEncoder {
+    /// Color of one LED.
+    type Color;
+
+    /// Number of pulse codes, usually one per bit, sent for every LED.
+    const PULSES_PER_LED: usize;
+
+    /// Write the pulse codes of `color` into `out`, which holds exactly
+    /// [LedEncoder::PULSES_PER_LED] of them.
+    ///
+    /// `config` is the configuration of the adapter, e.g. to apply its color
+    /// order or correction.
+    fn encode(
+        &mut self,
+        color: Self::Color,
+        pulses: &BitPulses,
+        config: &AdapterConfig,
+        out: &mut [u32],
+    );
+
+    /// Called before the first LED of every frame is encoded, e.g. for
+    /// encoders that keep state per LED.
+    fn start_frame(&mut self) {}
+
+    /// Sum of the 8-bit channel values [LedEncoder::encode] sends for
+    /// `color`, which the adapters use to estimate the current drawn by a
+    /// frame for [AdapterConfig::with_power_limit].
+    ///
+    /// The power limit works by lowering the brightness of the configuration,
+    /// so encoders that ignore it should keep the default of 0, which leaves
+    /// their frames unlimited.
+    fn load(&self, color: &Self::Color, config: &AdapterConfig) -> u32 {
+        let _ = (color, config);
+        0
+    }
+}
+
+/// Encoder of RGB colors with 8 bits per channel, applying the color order
+/// and corrections of the configuration like [SmartLedsAdapter](crate::SmartLedsAdapter).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rgb8Encoder;
+
+impl LedEncoder for Rgb8Encoder {
+    type Color = RGB8;
+    const PULSES_PER_LED: usize = 24;
+
+    fn encode(&mut self, color: RGB8, pulses: &BitPulses, config: &AdapterConfig, out: &mut [u32]) {
+        RgbBitsEncoder::<8>.encode(color, pulses, config, out);
+    }
+
+    fn load(&self, color: &RGB8, config: &AdapterConfig) -> u32 {
+        rgb_load(*color, config)
+    }
+}
+
+/// Encoder of RGBW colors with 8 bits per channel, sent in the order of the
+/// configuration followed by the white channel, like
+/// [SmartLedsAdapterRgbw](crate::SmartLedsAdapterRgbw).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RgbwEncoder;
+
+impl LedEncoder for RgbwEncoder {
+    type Color = RGBW<u8>;
+    const PULSES_PER_LED: usize = 32;
+
+    fn encode(
+        &mut self,
+        color: RGBW<u8>,
+        pulses: &BitPulses,
+        config: &AdapterConfig,
+        out: &mut [u32],
+    ) {
+        let (rgb, white) = out.split_at_mut(24);
+        Rgb8Encoder.encode(RGB8::new(color.r, color.g, color.b), pulses, config, rgb);
+        pulses.write_bits(correct_channel(color.a.0, config) as u32, white);
+    }
+
+    fn load(&self, color: &RGBW<u8>, config: &AdapterConfig) -> u32 {
+        let rgb = RGB8::new(color.r, color.g, color.b);
+        rgb_load(rgb, config) + correct_channel(color.a.0, config) as u32
+    }
+}
+
+/// Converts RGB colors to RMT pulse codes using the timing, color order and
+/// polarity of an [AdapterConfig].
+#[derive(Debug, Clone, Copy)]
+pub struct Encoder {
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+}
+
+impl Encoder {
+    /// Create an encoder for the given configuration.
+    ///
+    /// Use [ClockSource::Explicit](crate::ClockSource::Explicit) to encode
+    /// without setting up the clocks, e.g. on the host.
+    ///
+    /// # Panics
+    ///
+    /// If the pulse widths can't be sent at the tick rate of the
+    /// configuration, see [Encoder::try_new].
+    pub fn new(config: AdapterConfig) -> Self {
+        Self::try_new(config).unwrap()
+    }
+
+    /// Create an encoder for the given configuration, returning
+    /// [LedAdapterError::TimingOutOfRange] instead of panicking if the pulse
+    /// widths can't be sent at its tick rate.
+    pub fn try_new(config: AdapterConfig) -> Result<Self, LedAdapterError> {
+        let tick_hz = config.tick_rate().as_hz();
+
+        Ok(Self {
+            pulses: led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+        })
+    }
+
+    /// Encode all colors of the iterator followed by the end delimiter into
+    /// `buffer` and return the number of LEDs encoded.
+    ///
+    /// The buffer has to be sized with [buffer_size](crate::buffer_size) for
+    /// at least as many LEDs as the iterator provides. The iterator is cloned
+    /// to apply [AdapterConfig::with_power_limit] before encoding.
+    pub fn encode<T, I>(&self, iterator: T, buffer: &mut [u32]) -> Result<usize, LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        T::IntoIter: Clone,
+        I: Into<RGB8>,
+    {
+        let iterator = iterator.into_iter();
+        let mut power =
+            PowerScale::of_colors(&Rgb8Encoder, iterator.clone().map(Into::into), &self.config);
+        encode_frame(
+            &mut Rgb8Encoder,
+            buffer,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut power,
+            self.reset_pulse,
+        )
+    }
+}
+
+/// Encode a slice of colors into `buffer` using the given pulse widths and
+/// the default color order, and return the number of LEDs encoded.
+///
+/// `tick_hz` is the tick rate of the RMT channel the pulses are sent with,
+/// i.e. the rate of its clock source divided by its clock divider, e.g.
+/// `80_000_000` for the APB clock without a divider. It is taken as is, so
+/// this works without any clocks, e.g. on the host.
+pub fn encode_rgb_into(
+    colors: &[RGB8],
+    buffer: &mut [u32],
+    timing: &LedTiming,
+    tick_hz: u32,
+) -> Result<usize, LedAdapterError> {
+    let pulses =
+        pulses::bit_pulses(tick_hz, timing, false).ok_or(LedAdapterError::TimingOutOfRange)?;
+    let reset_pulse = pulses::reset_pulse(tick_hz, timing, false);
+    let leds = colors.iter().map(|color| [color.g, color.r, color.b]);
+
+    pulses::encode_channels(leds, &pulses, reset_pulse, buffer).map_err(|encoded| {
+        LedAdapterError::BufferSizeExceeded {
+            needed: colors.len(),
+            capacity: buffer.len().saturating_sub(1) / RMT_RAM_ONE_LED,
+            encoded,
+        }
+    })
+}
@@ -0,0 +1,60 @@
+//! Type-erased smart LED writers, to handle adapters of different types and
+//! buffer sizes alike.
+
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+use crate::LedAdapterError;
+
+/// Object safe version of [SmartLedsWrite], implemented for all writers of
+/// RGB8 colors that fail with [LedAdapterError].
+///
+/// [SmartLedsWrite::write] is generic, so `dyn SmartLedsWrite` isn't
+/// possible. Use `&mut dyn DynSmartLedsWrite` instead, or wrap it in a
+/// [DynSmartLed] to get a [SmartLedsWrite] again.
+pub trait DynSmartLedsWrite {
+    /// Write all colors of the iterator, see [SmartLedsWrite::write].
+    fn write_dyn(&mut self, colors: &mut dyn Iterator<Item = RGB8>) -> Result<(), LedAdapterError>;
+}
+
+impl<W> DynSmartLedsWrite for W
+where
+    W: SmartLedsWrite<Color = RGB8, Error = LedAdapterError>,
+{
+    fn write_dyn(&mut self, colors: &mut dyn Iterator<Item = RGB8>) -> Result<(), LedAdapterError> {
+        self.write(colors)
+    }
+}
+
+/// Smart LED writer erasing the type of the writer it borrows, e.g. to pass
+/// adapters with different buffer sizes to a function that isn't generic.
+///
+/// ```rust,ignore
+/// fn animate(led: &mut DynSmartLed<'_>) { /* ... */ }
+///
+/// animate(&mut DynSmartLed::new(&mut small));
+/// animate(&mut DynSmartLed::new(&mut large));
+/// ```
+pub struct DynSmartLed<'a> {
+    writer: &'a mut dyn DynSmartLedsWrite,
+}
+
+impl<'a> DynSmartLed<'a> {
+    /// Erase the type of `writer`.
+    pub fn new(writer: &'a mut dyn DynSmartLedsWrite) -> Self {
+        Self { writer }
+    }
+}
+
+impl SmartLedsWrite for DynSmartLed<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        self.writer
+            .write_dyn(&mut iterator.into_iter().map(Into::into))
+    }
+}
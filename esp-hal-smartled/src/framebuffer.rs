@@ -0,0 +1,96 @@
+//! An owned framebuffer for random-access updates of the LED colors.
+
+use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB8};
+
+/// Framebuffer of `N` colors on top of a smart LED writer.
+///
+/// Instead of building an iterator for every frame, the colors are updated in
+/// place, e.g. with [Framebuffer::set_pixel], and sent to the LEDs with
+/// [Framebuffer::present].
+///
+/// ```rust,ignore
+/// let mut fb = Framebuffer::<_, 64>::new(led);
+/// fb.set_pixel(5, RED);
+/// fb.present().unwrap();
+/// ```
+pub struct Framebuffer<W, const N: usize> {
+    writer: W,
+    pixels: [RGB8; N],
+}
+
+impl<W, const N: usize> Framebuffer<W, N> {
+    /// Create a new framebuffer with all LEDs turned off.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pixels: [RGB8::default(); N],
+        }
+    }
+
+    /// The colors of the framebuffer.
+    pub fn pixels(&self) -> &[RGB8; N] {
+        &self.pixels
+    }
+
+    /// Mutable access to the colors of the framebuffer.
+    ///
+    /// Changes are only sent to the LEDs on the next [Framebuffer::present].
+    pub fn pixels_mut(&mut self) -> &mut [RGB8; N] {
+        &mut self.pixels
+    }
+
+    /// The color of one LED, `None` if `index` is out of bounds.
+    pub fn pixel(&self, index: usize) -> Option<RGB8> {
+        self.pixels.get(index).copied()
+    }
+
+    /// Set the color of one LED.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn set_pixel(&mut self, index: usize, color: RGB8) {
+        self.pixels[index] = color;
+    }
+
+    /// Set all LEDs to the same color.
+    pub fn fill(&mut self, color: RGB8) {
+        self.pixels = [color; N];
+    }
+
+    /// Turn all LEDs off.
+    pub fn clear(&mut self) {
+        self.fill(RGB8::default());
+    }
+
+    /// Iterate over the colors of the framebuffer, e.g. to update all of them
+    /// in a loop.
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, RGB8> {
+        self.pixels.iter_mut()
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, const N: usize> Framebuffer<W, N>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    /// Encode and transmit the framebuffer.
+    pub fn present(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied())
+    }
+}
+
+impl<W, const N: usize> Framebuffer<W, N>
+where
+    W: SmartLedsWriteAsync<Color = RGB8>,
+{
+    /// Encode and transmit the framebuffer.
+    pub async fn present_async(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied()).await
+    }
+}
@@ -0,0 +1,129 @@
+//! [DrawTarget] for rendering `embedded-graphics` onto LED matrices.
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{DrawTarget, OriginDimensions, Pixel, RgbColor, Size},
+};
+use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB8};
+
+use crate::PixelMapping;
+
+/// LED matrix of up to `N` LEDs on top of a smart LED writer, implementing
+/// [DrawTarget] so text, shapes and images of `embedded-graphics` can be
+/// drawn onto it.
+///
+/// Drawing only updates the pixels in memory, the [PixelMapping] places them
+/// on the strip when the matrix is sent to the LEDs with [LedMatrix::flush].
+/// Pixels outside of the matrix or beyond the first `N` LEDs are ignored.
+///
+/// ```rust,ignore
+/// let mut matrix = LedMatrix::<_, _, 64>::new(led, Progressive::new(8, 8));
+/// Circle::new(Point::new(1, 1), 6)
+///     .into_styled(PrimitiveStyle::with_stroke(Rgb888::RED, 1))
+///     .draw(&mut matrix)
+///     .unwrap();
+/// matrix.flush().unwrap();
+/// ```
+pub struct LedMatrix<W, M, const N: usize> {
+    writer: W,
+    map: M,
+    pixels: [RGB8; N],
+}
+
+impl<W, M, const N: usize> LedMatrix<W, M, N>
+where
+    M: PixelMapping,
+{
+    /// Create a new matrix with all LEDs turned off.
+    pub fn new(writer: W, map: M) -> Self {
+        Self {
+            writer,
+            map,
+            pixels: [RGB8::default(); N],
+        }
+    }
+
+    /// The mapping of the pixels to the LEDs.
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+
+    /// The colors of the LEDs, in the order of the strip.
+    pub fn pixels(&self) -> &[RGB8; N] {
+        &self.pixels
+    }
+
+    /// Mutable access to the colors of the LEDs, in the order of the strip.
+    pub fn pixels_mut(&mut self) -> &mut [RGB8; N] {
+        &mut self.pixels
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, M, const N: usize> LedMatrix<W, M, N>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    /// Encode and transmit the matrix.
+    pub fn flush(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied())
+    }
+}
+
+impl<W, M, const N: usize> LedMatrix<W, M, N>
+where
+    W: SmartLedsWriteAsync<Color = RGB8>,
+{
+    /// Encode and transmit the matrix.
+    pub async fn flush_async(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied()).await
+    }
+}
+
+impl<W, M, const N: usize> OriginDimensions for LedMatrix<W, M, N>
+where
+    M: PixelMapping,
+{
+    fn size(&self) -> Size {
+        Size::new(self.map.width(), self.map.height())
+    }
+}
+
+impl<W, M, const N: usize> DrawTarget for LedMatrix<W, M, N>
+where
+    M: PixelMapping,
+{
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) else {
+                continue;
+            };
+            if let Some(pixel) = self
+                .map
+                .index(x, y)
+                .and_then(|index| self.pixels.get_mut(index))
+            {
+                *pixel = RGB8::new(color.r(), color.g(), color.b());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels = [RGB8::new(color.r(), color.g(), color.b()); N];
+        Ok(())
+    }
+}
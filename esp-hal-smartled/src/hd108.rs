@@ -0,0 +1,94 @@
+//! Support for clocked HD108 LEDs with 16 bits per color channel, driven by an
+//! SPI peripheral.
+
+use esp_hal::{spi::master::Spi, Blocking};
+use smart_leds_trait::{SmartLedsWrite, RGB16};
+
+use crate::LedAdapterError;
+
+// Maximum value of the 5-bit gain fields.
+const MAX_GAIN: u8 = 0x1f;
+
+/// Adapter taking a blocking SPI driver and providing HD108 LED interaction
+/// functionality using the `smart-leds` crate
+///
+/// Connect the LED clock line to SCK and the data line to MOSI of the SPI
+/// driver.
+pub struct Hd108Adapter<'d> {
+    spi: Spi<'d, Blocking>,
+    gain: u8,
+}
+
+impl<'d> Hd108Adapter<'d> {
+    /// Create a new adapter object that drives the LEDs using the SPI driver,
+    /// with the gain set to its maximum.
+    pub fn new(spi: Spi<'d, Blocking>) -> Self {
+        Self {
+            spi,
+            gain: MAX_GAIN,
+        }
+    }
+
+    /// Set the 5-bit gain sent for every color channel of every LED. Values
+    /// above 31 are clamped.
+    pub fn with_gain(mut self, gain: u8) -> Self {
+        self.set_gain(gain);
+        self
+    }
+
+    /// Set the 5-bit gain sent for every color channel of every LED. Values
+    /// above 31 are clamped.
+    pub fn set_gain(&mut self, gain: u8) {
+        self.gain = gain.min(MAX_GAIN);
+    }
+
+    /// The 5-bit gain sent for every color channel of every LED.
+    pub fn gain(&self) -> u8 {
+        self.gain
+    }
+
+    /// Return the underlying SPI driver.
+    pub fn into_inner(self) -> Spi<'d, Blocking> {
+        self.spi
+    }
+}
+
+impl SmartLedsWrite for Hd108Adapter<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB16;
+
+    /// Send the start frame, one frame per item of the iterator and the end
+    /// frame over SPI.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        // Start frame
+        self.spi.write(&[0x00; 16])?;
+
+        // A start bit followed by the red, green and blue gain
+        let gain = self.gain as u16;
+        let header = (0x8000 | (gain << 10) | (gain << 5) | gain).to_be_bytes();
+
+        let mut led_count: usize = 0;
+        for item in iterator {
+            let color = item.into();
+            let [r0, r1] = color.r.to_be_bytes();
+            let [g0, g1] = color.g.to_be_bytes();
+            let [b0, b1] = color.b.to_be_bytes();
+            self.spi
+                .write(&[header[0], header[1], r0, r1, g0, g1, b0, b1])?;
+            led_count += 1;
+        }
+
+        // Additional clock edges to shift the data through the whole strip.
+        // Zeros lack the start bit, so they don't light any LED past the end
+        // of the strip.
+        for _ in 0..led_count.div_ceil(16) + 4 {
+            self.spi.write(&[0x00])?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+//! Colors in the HSV color space.
+
+use smart_leds_trait::RGB8;
+
+/// A color given as hue, saturation and value, all ranging from 0 to 255.
+///
+/// It converts into [RGB8], so iterators of it can be written to any adapter
+/// taking RGB colors directly. The conversion only uses integer arithmetic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Hsv {
+    /// The hue, going around the color wheel from red over green and blue
+    /// back to red.
+    pub hue: u8,
+    /// The saturation, from white to the pure color.
+    pub sat: u8,
+    /// The value, from black to full brightness.
+    pub val: u8,
+}
+
+impl Hsv {
+    /// Create a color from its hue, saturation and value.
+    pub const fn new(hue: u8, sat: u8, val: u8) -> Self {
+        Self { hue, sat, val }
+    }
+}
+
+impl From<Hsv> for RGB8 {
+    fn from(hsv: Hsv) -> Self {
+        let v = hsv.val as u16;
+        let s = hsv.sat as u16;
+
+        // The hue wheel is split into six sectors of about 43 steps, with
+        // `f` being the position within the sector scaled to 0..=255.
+        let sector = hsv.hue / 43;
+        let f = (hsv.hue % 43) as u16 * 255 / 42;
+
+        let p = (v * (255 - s) / 255) as u8;
+        let q = (v * (255 - s * f / 255) / 255) as u8;
+        let t = (v * (255 - s * (255 - f) / 255) / 255) as u8;
+        let v = v as u8;
+
+        match sector {
+            0 => RGB8::new(v, t, p),
+            1 => RGB8::new(q, v, p),
+            2 => RGB8::new(p, v, t),
+            3 => RGB8::new(p, q, v),
+            4 => RGB8::new(t, p, v),
+            _ => RGB8::new(v, p, q),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Writing to several async LED strips at once.
+
+use core::{
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::Poll,
+};
+
+use smart_leds_trait::SmartLedsWriteAsync;
+
+/// Write one frame to each of several writers, e.g. [SmartLedsAdapterAsync]s
+/// on different RMT channels, transmitting all of them concurrently.
+///
+/// The writes run interleaved in the current task, so the total time
+/// approaches the time of the longest frame rather than the sum of all of
+/// them. The returned array holds the result of each write at the same
+/// position as its writer; a failed write doesn't affect the others.
+///
+/// ```rust,ignore
+/// let [left, right] = join_write([(&mut led_left, frame_left), (&mut led_right, frame_right)]).await;
+/// ```
+///
+/// [SmartLedsAdapterAsync]: crate::SmartLedsAdapterAsync
+pub async fn join_write<W, T, I, const N: usize>(
+    writes: [(&mut W, T); N],
+) -> [Result<(), W::Error>; N]
+where
+    W: SmartLedsWriteAsync,
+    T: IntoIterator<Item = I>,
+    I: Into<W::Color>,
+{
+    let mut results = [const { Ok(()) }; N];
+    let mut futures = pin!(writes.map(|(writer, frame)| Some(writer.write(frame))));
+
+    poll_fn(|cx| {
+        // SAFETY: The futures are never moved out of the pinned array, they are
+        // only dropped in place once they are done.
+        let futures = unsafe { futures.as_mut().get_unchecked_mut() };
+        let mut pending = false;
+        for (slot, result) in futures.iter_mut().zip(results.iter_mut()) {
+            if let Some(future) = slot {
+                // SAFETY: See above.
+                match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(r) => {
+                        *result = r;
+                        *slot = None;
+                    }
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+
+    results
+}
@@ -33,102 +33,1818 @@
 //! }
 //! ```
 //!
+//! ## Panics
+//!
+//! Failures at runtime are reported as [LedAdapterError]. The only panics
+//! are in the constructors without a `try_` prefix, which unwrap the result
+//! of their `try_` counterpart for convenience, and in accessors taking an
+//! index that is out of bounds, which document it. Invalid buffer sizes are
+//! rejected at compile time.
+//!
 //! ## Feature Flags
 #![doc = document_features::document_features!()]
 #![doc(html_logo_url = "https://avatars.githubusercontent.com/u/46717278")]
 #![deny(missing_docs)]
 #![no_std]
 
-use core::{fmt::Debug, slice::IterMut};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::{
+    fmt::Debug,
+    future::{poll_fn, Future},
+    pin::pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
 
+#[cfg(any(feature = "esp32", feature = "esp32s3"))]
+use esp_hal::system::Cpu;
 use esp_hal::{
     clock::Clocks,
-    gpio::{interconnect::PeripheralOutput, Level},
+    delay::Delay,
+    gpio::{interconnect::PeripheralOutput, DriveMode, Level, OutputConfig},
+    interrupt::{self, Error as InterruptError, Priority},
+    peripherals::Interrupt,
     rmt::{
-        Channel, Error as RmtError, PulseCode, RawChannelAccess, TxChannel, TxChannelAsync,
-        TxChannelConfig, TxChannelCreator, TxChannelInternal,
+        Channel, ContinuousTxTransaction, Error as RmtError, RawChannelAccess,
+        SingleShotTxTransaction, TxChannel, TxChannelAsync, TxChannelConfig, TxChannelCreator,
+        TxChannelInternal,
     },
-    Async, Blocking,
+    spi::Error as SpiError,
+    time::{Duration, Instant, Rate},
+    Async, Blocking, DriverMode,
 };
-use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB8};
+use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB16, RGB8};
+
+/// Emits a warning through `defmt` or `log`, whichever is enabled.
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "defmt")]
+        defmt::warn!($($arg)*);
+        #[cfg(all(feature = "log", not(feature = "defmt")))]
+        log::warn!($($arg)*);
+    };
+}
+
+mod apa102;
+#[cfg(feature = "smart-leds-trait-0-2")]
+mod compat;
+mod compositor;
+mod custom;
+mod dither;
+mod encoded_frame;
+pub mod encoder;
+mod erased;
+mod framebuffer;
+#[cfg(feature = "embedded-graphics")]
+mod graphics;
+mod hd108;
+mod hsv;
+mod join;
+mod lpd8806;
+mod matrix;
+mod multi;
+mod p9813;
+#[cfg(feature = "embassy-time")]
+mod pacer;
+mod pipeline;
+mod pulses;
+mod queued;
+mod recorder;
+#[cfg(feature = "embassy-sync")]
+mod renderer;
+mod rgbw;
+mod segments;
+mod slice;
+mod status;
+mod streaming;
+mod strip;
+mod tm1814;
+mod transfer;
+
+use encoder::{BitPulses, LedEncoder, Rgb8Encoder};
+use pulses::{is_end_marker, pulse_code_ticks};
+
+pub use apa102::Apa102Adapter;
+pub use compositor::{Compositor, Layer};
+pub use custom::{buffer_size_custom, SmartLedsAdapterCustom};
+pub use dither::TemporalDither;
+pub use encoded_frame::EncodedFrame;
+pub use erased::{DynSmartLed, DynSmartLedsWrite};
+pub use framebuffer::Framebuffer;
+#[cfg(feature = "embedded-graphics")]
+pub use graphics::LedMatrix;
+pub use hd108::Hd108Adapter;
+pub use hsv::Hsv;
+pub use join::join_write;
+pub use lpd8806::Lpd8806Adapter;
+pub use matrix::{Corner, MatrixMap, PixelMapping, Progressive, Rotation, Wiring};
+pub use multi::MultiStripAdapter;
+pub use p9813::P9813Adapter;
+#[cfg(feature = "embassy-time")]
+pub use pacer::FramePacer;
+pub use pipeline::{Brightness, ColorPipeline, ColorStage, Correction, Curve, Custom};
+pub use pulses::LedTiming;
+pub use queued::SmartLedsAdapterQueued;
+pub use recorder::FrameRecorder;
+#[cfg(feature = "embassy-sync")]
+pub use renderer::{run_renderer, RenderCommand};
+pub use rgbw::{buffer_size_rgbw, SmartLedsAdapterRgbw, WhiteMode};
+pub use segments::{Segment, SegmentedStrip};
+#[cfg(feature = "alloc")]
+pub use slice::SmartLedsAdapterBoxed;
+pub use slice::{SmartLedsAdapterRef, SmartLedsAdapterSlice};
+pub use status::{StatusLed, StatusPattern};
+pub use streaming::SmartLedsAdapterStreaming;
+pub use strip::{LedStripBuffer, SmartLedStrip};
+pub use tm1814::{buffer_size_tm1814, Tm1814Adapter};
+pub use transfer::WriteTransfer;
 
 // Required RMT RAM to drive one LED.
 // number of channels (r,g,b -> 3) * pulses per channel 8)
-const RMT_RAM_ONE_LED: usize = 3 * 8;
+const RMT_RAM_ONE_LED: usize = rmt_ram_one_led(8);
+
+// Required RMT RAM to drive one LED with the given number of bits per channel.
+const fn rmt_ram_one_led(bits_per_channel: usize) -> usize {
+    3 * bits_per_channel
+}
+
+// Number of pulse codes of one block of RMT RAM, which a channel uses by
+// default.
+#[cfg(any(feature = "esp32", feature = "esp32s2"))]
+const RMT_BLOCK_SIZE: usize = 64;
+#[cfg(not(any(feature = "esp32", feature = "esp32s2")))]
+const RMT_BLOCK_SIZE: usize = 48;
+
+// Number of blocks of RMT RAM.
+#[cfg(any(feature = "esp32", feature = "esp32s3"))]
+const RMT_BLOCKS: u8 = 8;
+#[cfg(not(any(feature = "esp32", feature = "esp32s3")))]
+const RMT_BLOCKS: u8 = 4;
+
+/// All types of errors that can happen during the conversion and transmission
+/// of LED commands
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LedAdapterError {
+    /// Raised in the event that the provided data container is not large enough
+    BufferSizeExceeded {
+        /// Number of LEDs that were attempted to be written. When writing from
+        /// an iterator, this is a lower bound as the iterator isn't drained.
+        needed: usize,
+        /// Number of LEDs the buffer can hold.
+        capacity: usize,
+        /// Number of LEDs encoded into the buffer before it ran out, i.e. the
+        /// index of the first LED that didn't fit. The buffer holds a partial
+        /// frame from then on, unless this is 0 because the length was
+        /// checked up front.
+        encoded: usize,
+    },
+    /// Raised if all LED data fit into the buffer but there is no slot left for
+    /// the end delimiter. The buffer was likely sized with the wrong helper,
+    /// e.g. [buffer_size_async] for the blocking [SmartLedsAdapter].
+    DelimiterSlotMissing,
+    /// Raised if something goes wrong in the transmission,
+    TransmissionError(RmtError),
+    /// Raised by the SPI based adapters, e.g. [Apa102Adapter], if something
+    /// goes wrong in the SPI transmission
+    SpiError(SpiError),
+    /// Raised by [SmartLedsAdapterAsync] if a transmission fails part way
+    /// through a frame. Only the first `sent` LEDs have been updated.
+    PartialTransmission {
+        /// The underlying RMT error
+        error: RmtError,
+        /// Number of LEDs that were sent successfully before the error
+        sent: usize,
+    },
+    /// Raised by [SmartLedsAdapterAsync] if the line was idle for longer than
+    /// the reset time of the LEDs between two transactions of a frame, e.g.
+    /// because the executor was busy with other tasks. The LEDs latched the
+    /// first `sent` LEDs, and the rest of the frame isn't sent as it would
+    /// overwrite them.
+    FrameSplit {
+        /// Number of LEDs sent before the line was idle for too long
+        sent: usize,
+    },
+    /// Raised if the RMT channel was lost during a previous failed
+    /// transmission and can no longer be used
+    ChannelUnavailable,
+    /// Raised when creating an adapter with a buffer of `len` elements, which
+    /// wasn't sized with [buffer_size]
+    InvalidBufferSize {
+        /// Length of the buffer
+        len: usize,
+    },
+    /// Raised when writing to an adapter created with
+    /// [SmartLedsAdapter::new_uninit] before it was initialized
+    NotInitialized,
+    /// Raised by [SmartLedsAdapter::write_timeout] if the transmission didn't
+    /// finish in time. The RMT driver can't abort a transmission, so the frame
+    /// has been sent completely nevertheless.
+    Timeout,
+    /// Raised if the adapter can't drive the given chip, e.g. an RGB adapter
+    /// and a chip with a white channel, see [Chipset::channels].
+    UnsupportedChipset(Chipset),
+    /// Raised by [SmartLedsAdapterAsync::set_interrupt_priority] if the RMT
+    /// interrupt can't be enabled with the given priority.
+    InterruptError(InterruptError),
+    /// Raised by [SmartLedsAdapter::write_raw] if the pulses don't end with an
+    /// end delimiter, i.e. a pulse code with a length of zero.
+    EndDelimiterMissing,
+    /// Raised if a pulse width of the timing can't be sent at the tick rate
+    /// of the configuration, see [AdapterConfig::tick_rate]: either it is
+    /// shorter than one tick because the clock is too slow, or it is longer
+    /// than the 32767 ticks a pulse code can hold.
+    TimingOutOfRange,
+    /// Raised if a frame should be repeated with
+    /// [AdapterConfig::with_auto_refresh] but doesn't fit into the RAM of the
+    /// channel. The frame isn't sent.
+    FrameTooLongToRepeat {
+        /// Number of pulse codes of the frame, including the end delimiter
+        len: usize,
+        /// Number of pulse codes the RAM of the channel can hold
+        ram: usize,
+    },
+    /// Raised by [SmartLedsAdapterAsync::start_write] if the previous frame is
+    /// still being sent.
+    Busy,
+}
+
+impl core::fmt::Display for LedAdapterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LedAdapterError::BufferSizeExceeded {
+                needed,
+                capacity,
+                encoded,
+            } => write!(
+                f,
+                "{needed} LEDs don't fit into a buffer for {capacity} LEDs, {encoded} were encoded"
+            ),
+            LedAdapterError::DelimiterSlotMissing => {
+                write!(f, "no slot left for the end delimiter in the buffer")
+            }
+            LedAdapterError::TransmissionError(e) => write!(f, "RMT transmission failed: {e:?}"),
+            LedAdapterError::SpiError(e) => write!(f, "SPI transmission failed: {e:?}"),
+            LedAdapterError::PartialTransmission { error, sent } => {
+                write!(f, "RMT transmission failed after {sent} LEDs: {error:?}")
+            }
+            LedAdapterError::FrameSplit { sent } => {
+                write!(f, "the LEDs latched the frame after {sent} LEDs")
+            }
+            LedAdapterError::ChannelUnavailable => {
+                write!(f, "the RMT channel was lost in a failed transmission")
+            }
+            LedAdapterError::InvalidBufferSize { len } => {
+                write!(
+                    f,
+                    "a buffer of {len} pulse codes wasn't sized with `buffer_size`"
+                )
+            }
+            LedAdapterError::NotInitialized => write!(f, "the adapter isn't initialized"),
+            LedAdapterError::Timeout => write!(f, "the RMT transmission timed out"),
+            LedAdapterError::InterruptError(e) => {
+                write!(f, "the RMT interrupt can't be enabled: {e:?}")
+            }
+            LedAdapterError::UnsupportedChipset(chipset) => {
+                write!(f, "{:?} chips aren't supported by this adapter", chipset)
+            }
+            LedAdapterError::EndDelimiterMissing => {
+                write!(f, "the pulses don't end with an end delimiter")
+            }
+            LedAdapterError::TimingOutOfRange => {
+                write!(f, "the pulse widths can't be sent at the tick rate")
+            }
+            LedAdapterError::FrameTooLongToRepeat { len, ram } => write!(
+                f,
+                "a frame of {len} pulse codes can't be repeated from {ram} codes of RMT RAM"
+            ),
+            LedAdapterError::Busy => write!(f, "the previous frame is still being sent"),
+        }
+    }
+}
+
+impl core::error::Error for LedAdapterError {}
+
+impl From<RmtError> for LedAdapterError {
+    fn from(e: RmtError) -> Self {
+        LedAdapterError::TransmissionError(e)
+    }
+}
+
+impl From<SpiError> for LedAdapterError {
+    fn from(e: SpiError) -> Self {
+        LedAdapterError::SpiError(e)
+    }
+}
+
+impl From<InterruptError> for LedAdapterError {
+    fn from(e: InterruptError) -> Self {
+        LedAdapterError::InterruptError(e)
+    }
+}
+
+// Levels of the high and low part of a pulse, swapped for an inverted line.
+fn pulse_levels(inverted: bool) -> (Level, Level) {
+    if inverted {
+        (Level::Low, Level::High)
+    } else {
+        (Level::High, Level::Low)
+    }
+}
+
+// Pulse codes of a 0 and a 1 bit, see `pulses::bit_pulses`.
+fn led_pulses_for_clock(
+    tick_hz: u32,
+    timing: Timing,
+    inverted: bool,
+) -> Result<BitPulses, LedAdapterError> {
+    pulses::bit_pulses(tick_hz, &timing.led_timing(), inverted)
+        .ok_or(LedAdapterError::TimingOutOfRange)
+}
+
+// The end delimiter, see `pulses::reset_pulse`.
+fn reset_pulse_for_clock(tick_hz: u32, timing: Timing, inverted: bool) -> u32 {
+    pulses::reset_pulse(tick_hz, &timing.led_timing(), inverted)
+}
+
+/// State of the data line between frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IdleOutput {
+    /// Drive the line low, which is what the LEDs expect to latch a frame.
+    #[default]
+    Low,
+    /// Drive the line high.
+    High,
+    /// Don't drive an idle level, so the line keeps the level of the last
+    /// pulse.
+    HoldLast,
+}
+
+/// Source clock of the RMT peripheral, used to calculate the pulse lengths.
+///
+/// The adapters can't detect how the RMT peripheral was set up, so this has to
+/// match the rate passed to `Rmt::new`. Pass that rate with
+/// [ClockSource::Explicit] unless it is the rate of the APB or crystal clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockSource {
+    /// The APB clock.
+    #[default]
+    Apb,
+    /// The crystal oscillator clock.
+    Xtal,
+    /// An explicitly given clock rate.
+    Explicit(Rate),
+}
+
+impl ClockSource {
+    /// Rate of this clock source.
+    pub fn rate(&self) -> Rate {
+        match self {
+            ClockSource::Apb => Clocks::get().apb_clock,
+            ClockSource::Xtal => Clocks::get().xtal_clock,
+            ClockSource::Explicit(rate) => *rate,
+        }
+    }
+}
+
+/// Pulse timing of the LEDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Timing {
+    /// 800 kHz timing that works for SK68xx and WS2812 LEDs, see
+    /// [LedTiming::SK68XX].
+    #[default]
+    Sk68xx,
+    /// 400 kHz timing of WS2811 LEDs running in slow mode, see
+    /// [LedTiming::WS2811_SLOW].
+    Ws2811Slow,
+    /// 800 kHz timing of WS2815 LEDs, see [LedTiming::WS2815].
+    Ws2815,
+    /// 400 kHz timing of UCS1903 LEDs, see [LedTiming::UCS1903].
+    Ucs1903,
+    /// Custom pulse widths, e.g. for out-of-spec or clone chips.
+    Custom(LedTiming),
+}
+
+impl Timing {
+    /// Pulse widths of this timing.
+    pub const fn led_timing(&self) -> LedTiming {
+        match self {
+            Timing::Sk68xx => LedTiming::SK68XX,
+            Timing::Ws2811Slow => LedTiming::WS2811_SLOW,
+            Timing::Ws2815 => LedTiming::WS2815,
+            Timing::Ucs1903 => LedTiming::UCS1903,
+            Timing::Custom(timing) => *timing,
+        }
+    }
+}
+
+/// Order in which the color channels are sent to the LEDs.
+///
+/// For RGBW LEDs the white channel is always sent last, e.g. [ColorOrder::Grb]
+/// results in GRBW.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColorOrder {
+    /// Red, green, blue.
+    Rgb,
+    /// Red, blue, green.
+    Rbg,
+    /// Green, red, blue, which is what WS2812 and SK68xx LEDs expect.
+    #[default]
+    Grb,
+    /// Green, blue, red.
+    Gbr,
+    /// Blue, red, green.
+    Brg,
+    /// Blue, green, red.
+    Bgr,
+}
+
+impl ColorOrder {
+    // The red, green and blue channels in the order they are sent.
+    fn order<T>(&self, [r, g, b]: [T; 3]) -> [T; 3] {
+        match self {
+            ColorOrder::Rgb => [r, g, b],
+            ColorOrder::Rbg => [r, b, g],
+            ColorOrder::Grb => [g, r, b],
+            ColorOrder::Gbr => [g, b, r],
+            ColorOrder::Brg => [b, r, g],
+            ColorOrder::Bgr => [b, g, r],
+        }
+    }
+}
+
+/// Power budget of a strip, with a simple model of the current drawn by each
+/// LED, all in milliamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerLimit {
+    /// Maximum current the whole strip may draw.
+    pub max_current: u32,
+    /// Current drawn by one color channel at full brightness.
+    pub channel_current: u32,
+    /// Current drawn by every LED while it is off.
+    pub idle_current: u32,
+}
+
+impl PowerLimit {
+    /// A budget of `max_current` milliamps for WS2812B-like LEDs, which draw
+    /// about 20 mA per channel and 1 mA while off.
+    pub const fn new(max_current: u32) -> Self {
+        Self {
+            max_current,
+            channel_current: 20,
+            idle_current: 1,
+        }
+    }
+}
+
+/// Color temperatures of common light sources, to tint the LEDs with
+/// [AdapterConfig::with_color_temperature].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColorTemperature {
+    /// Candle light, 1900 K.
+    Candle,
+    /// A 40 W tungsten bulb, 2600 K.
+    Tungsten40W,
+    /// A 100 W tungsten bulb, 2850 K.
+    Tungsten100W,
+    /// A halogen lamp, 3200 K.
+    Halogen,
+    /// A carbon arc lamp, 5200 K.
+    CarbonArc,
+    /// The sun at high noon, 5400 K.
+    HighNoonSun,
+    /// Direct sunlight, 6000 K.
+    DirectSunlight,
+    /// An overcast sky, 7000 K.
+    OvercastSky,
+    /// A clear blue sky, 20000 K.
+    ClearBlueSky,
+    /// No tint.
+    #[default]
+    Uncorrected,
+}
+
+impl ColorTemperature {
+    /// The color of the light source, which the colors are scaled with.
+    pub const fn rgb(&self) -> RGB8 {
+        let (r, g, b) = match self {
+            ColorTemperature::Candle => (255, 147, 41),
+            ColorTemperature::Tungsten40W => (255, 197, 143),
+            ColorTemperature::Tungsten100W => (255, 214, 170),
+            ColorTemperature::Halogen => (255, 241, 224),
+            ColorTemperature::CarbonArc => (255, 250, 244),
+            ColorTemperature::HighNoonSun => (255, 255, 251),
+            ColorTemperature::DirectSunlight => (255, 255, 255),
+            ColorTemperature::OvercastSky => (201, 226, 255),
+            ColorTemperature::ClearBlueSky => (64, 156, 255),
+            ColorTemperature::Uncorrected => (255, 255, 255),
+        };
+        RGB8 { r, g, b }
+    }
+}
+
+/// Typical color corrections of LED types, matching the presets of FastLED,
+/// for [AdapterConfig::with_correction].
+///
+/// Presets for the color temperature such as `Tungsten100W` are part of
+/// [ColorTemperature].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColorCorrection {
+    /// SMD 5050 LEDs.
+    TypicalSmd5050,
+    /// LED strips, which are mostly made of SMD 5050 LEDs.
+    TypicalLedStrip,
+    /// 8 mm through-hole LEDs.
+    Typical8mmPixel,
+    /// Pixel strings, which are mostly made of 8 mm LEDs.
+    TypicalPixelString,
+    /// No correction.
+    #[default]
+    Uncorrected,
+}
+
+impl ColorCorrection {
+    /// The factors the red, green and blue channels are scaled with.
+    pub const fn rgb(&self) -> RGB8 {
+        let (r, g, b) = match self {
+            ColorCorrection::TypicalSmd5050 | ColorCorrection::TypicalLedStrip => (255, 176, 240),
+            ColorCorrection::Typical8mmPixel | ColorCorrection::TypicalPixelString => {
+                (255, 224, 140)
+            }
+            ColorCorrection::Uncorrected => (255, 255, 255),
+        };
+        RGB8 { r, g, b }
+    }
+}
+
+/// Common clockless LED chips, bundling everything needed to drive them.
+///
+/// Chips with a white channel need an adapter for RGBW colors, i.e.
+/// [SmartLedsAdapterRgbw] or, for the TM1814, [Tm1814Adapter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Chipset {
+    /// WS2812 and WS2812B.
+    Ws2812b,
+    /// WS2811 running at 800 kHz.
+    Ws2811,
+    /// WS2811 running in slow mode at 400 kHz.
+    Ws2811Slow,
+    /// WS2815.
+    Ws2815,
+    /// SK6812 RGB.
+    Sk6812,
+    /// SK6812 RGBW.
+    Sk6812W,
+    /// TM1814 RGBW.
+    Tm1814,
+    /// UCS1903.
+    Ucs1903,
+    /// TM1829. The first color channel is limited to 254, as the LEDs take
+    /// 255 as a configuration command.
+    Tm1829,
+}
+
+impl Chipset {
+    /// Adapter configuration with the timing, color order and polarity of
+    /// this chip.
+    pub const fn config(&self) -> AdapterConfig {
+        AdapterConfig::new().with_chipset(*self)
+    }
+
+    /// Number of color channels of this chip, 3 for RGB and 4 for RGBW.
+    pub const fn channels(&self) -> usize {
+        match self {
+            Chipset::Sk6812W | Chipset::Tm1814 => 4,
+            _ => 3,
+        }
+    }
+}
+
+/// Configuration for the smart LED adapters.
+///
+/// The default configuration drives the data line as a push-pull output, which
+/// is what directly connected LED strips expect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AdapterConfig {
+    open_drain: bool,
+    idle_output: IdleOutput,
+    min_frame_gap_ns: u32,
+    clock_source: ClockSource,
+    clock_divider: u8,
+    timing: Timing,
+    color_order: ColorOrder,
+    inverted: bool,
+    clamp_first_channel: bool,
+    memsize: u8,
+    auto_refresh: bool,
+    // Gamma as an 8.8 fixed point number, 0 if the correction is disabled
+    gamma: u16,
+    cie1931: bool,
+    gamma_table: [u8; 256],
+    brightness: u8,
+    correction: [u8; 3],
+    color_temperature: ColorTemperature,
+    // The correction multiplied with the color temperature
+    white_point: [u8; 3],
+    power_limit: Option<PowerLimit>,
+    transfer_tables: Option<&'static [[u8; 256]; 3]>,
+}
+
+impl Default for AdapterConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdapterConfig {
+    /// Create the default configuration, usable in const contexts.
+    pub const fn new() -> Self {
+        Self {
+            open_drain: false,
+            idle_output: IdleOutput::Low,
+            min_frame_gap_ns: 0,
+            clock_source: ClockSource::Apb,
+            clock_divider: 1,
+            timing: Timing::Sk68xx,
+            color_order: ColorOrder::Grb,
+            inverted: false,
+            clamp_first_channel: false,
+            memsize: 1,
+            auto_refresh: false,
+            gamma: 0,
+            cie1931: false,
+            gamma_table: gamma_table(0),
+            brightness: 255,
+            correction: [255; 3],
+            color_temperature: ColorTemperature::Uncorrected,
+            white_point: [255; 3],
+            power_limit: None,
+            transfer_tables: None,
+        }
+    }
+
+    /// Drive the data line as an open-drain output instead of push-pull.
+    ///
+    /// This is useful when the strip is driven through an external transistor
+    /// buffer with its own pull-up, e.g. for long cable runs. The pulse timing
+    /// is not affected.
+    ///
+    /// The RMT driver configures the pin with the default output settings of
+    /// esp-hal, whether or not this is set, so a drive strength or pull
+    /// resistor set on the pin beforehand is reset when the adapter is
+    /// created. The internal pull-up is not enabled, the line needs an
+    /// external one.
+    pub const fn with_open_drain(mut self, open_drain: bool) -> Self {
+        self.open_drain = open_drain;
+        self
+    }
+
+    /// Whether the data line is driven as an open-drain output.
+    pub const fn open_drain(&self) -> bool {
+        self.open_drain
+    }
+
+    /// Set the state of the data line between frames.
+    ///
+    /// Every frame ends with the line held low for the reset time of the
+    /// configured [Timing], which lets the LEDs latch it. This setting only
+    /// affects the line after that. Defaults to [IdleOutput::Low].
+    pub const fn with_idle_output(mut self, idle_output: IdleOutput) -> Self {
+        self.idle_output = idle_output;
+        self
+    }
+
+    /// State of the data line between frames.
+    pub const fn idle_output(&self) -> IdleOutput {
+        self.idle_output
+    }
+
+    /// Set the minimum time between the end of one frame and the start of the
+    /// next one.
+    ///
+    /// A write issued too soon after the previous one waits for the remainder
+    /// of the gap before transmitting, so that back-to-back frames don't get
+    /// glued together on the wire. Every frame already ends with the reset
+    /// time of the configured [Timing], so this is only needed for an
+    /// additional gap. The default of 0 disables this.
+    ///
+    /// Reset times too long for the end delimiter, i.e. longer than 32767
+    /// ticks of the RMT clock, are always waited for the same way, so that
+    /// the LEDs latch every frame even without a minimum gap.
+    pub const fn with_min_frame_gap_ns(mut self, min_frame_gap_ns: u32) -> Self {
+        self.min_frame_gap_ns = min_frame_gap_ns;
+        self
+    }
+
+    /// Minimum time between the end of one frame and the start of the next
+    /// one.
+    pub const fn min_frame_gap_ns(&self) -> u32 {
+        self.min_frame_gap_ns
+    }
+
+    // Time to wait after the end delimiter of a frame before the next one:
+    // the minimum frame gap or the part of the reset time the delimiter can't
+    // hold, whichever is longer.
+    fn frame_gap_ns(&self) -> u32 {
+        let reset = self.timing.led_timing().reset;
+        // The adapters reject a tick rate of 0 with `TimingOutOfRange`
+        let delimiter = (0x7fff * 1_000_000_000u64)
+            .checked_div(self.tick_rate().as_hz() as u64)
+            .map_or(u32::MAX, |ns| ns.min(u32::MAX as u64) as u32);
+        self.min_frame_gap_ns.max(reset.saturating_sub(delimiter))
+    }
+
+    /// Set the clock source the RMT peripheral is running from, i.e. the
+    /// rate passed to `Rmt::new`.
+    ///
+    /// Defaults to [ClockSource::Apb].
+    pub const fn with_clock_source(mut self, clock_source: ClockSource) -> Self {
+        self.clock_source = clock_source;
+        self
+    }
+
+    /// Clock source the RMT peripheral is running from.
+    pub const fn clock_source(&self) -> ClockSource {
+        self.clock_source
+    }
+
+    /// Set the divider of the RMT channel clock, dividing the rate of the
+    /// clock source further, e.g. to share the peripheral with protocols
+    /// that need longer pulses.
+    ///
+    /// A slower clock makes the pulse widths coarser, at 10 MHz they are
+    /// rounded down to multiples of 100 ns. If the shortest pulse of the
+    /// timing is less than one tick, the adapters can't be created and fail
+    /// with [LedAdapterError::TimingOutOfRange]. If the RMT peripheral itself runs
+    /// slower because `Rmt::new` was given a lower rate, pass that rate with
+    /// [ClockSource::Explicit] instead.
+    ///
+    /// The RMT channel can't divide by 0, so the adapters fail with
+    /// [LedAdapterError::TimingOutOfRange] for a divider of 0.
+    ///
+    /// Defaults to 1.
+    pub const fn with_clock_divider(mut self, clock_divider: u8) -> Self {
+        self.clock_divider = clock_divider;
+        self
+    }
+
+    /// Divider of the RMT channel clock.
+    pub const fn clock_divider(&self) -> u8 {
+        self.clock_divider
+    }
+
+    /// Rate the pulse widths are counted at, i.e. the rate of the clock
+    /// source divided by the clock divider, or 0 for a divider of 0.
+    pub fn tick_rate(&self) -> Rate {
+        let rate = self.clock_source.rate().as_hz();
+        Rate::from_hz(rate.checked_div(self.clock_divider as u32).unwrap_or(0))
+    }
+
+    /// Set the pulse timing of the LEDs.
+    ///
+    /// Defaults to [Timing::Sk68xx].
+    pub const fn with_timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Pulse timing of the LEDs.
+    pub const fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    /// Set the order in which the color channels are sent to the LEDs.
+    ///
+    /// Defaults to [ColorOrder::Grb].
+    pub const fn with_color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = color_order;
+        self
+    }
+
+    /// Order in which the color channels are sent to the LEDs.
+    pub const fn color_order(&self) -> ColorOrder {
+        self.color_order
+    }
+
+    /// Set the timing, color order and polarity to those of the given chip.
+    pub const fn with_chipset(self, chipset: Chipset) -> Self {
+        let (timing, color_order) = match chipset {
+            Chipset::Ws2812b | Chipset::Sk6812 | Chipset::Sk6812W => {
+                (Timing::Sk68xx, ColorOrder::Grb)
+            }
+            Chipset::Ws2811 => (Timing::Sk68xx, ColorOrder::Rgb),
+            Chipset::Ws2811Slow => (Timing::Ws2811Slow, ColorOrder::Rgb),
+            Chipset::Ws2815 => (Timing::Ws2815, ColorOrder::Grb),
+            Chipset::Tm1814 => (Timing::Custom(LedTiming::TM1814), ColorOrder::Rgb),
+            Chipset::Ucs1903 => (Timing::Ucs1903, ColorOrder::Rgb),
+            Chipset::Tm1829 => (Timing::Custom(LedTiming::TM1829), ColorOrder::Rgb),
+        };
+
+        let mut config = self
+            .with_timing(timing)
+            .with_color_order(color_order)
+            .with_inverted(matches!(chipset, Chipset::Tm1814 | Chipset::Tm1829));
+        // The TM1829 takes 0xff in the first channel as a configuration command
+        config.clamp_first_channel = matches!(chipset, Chipset::Tm1829);
+        config
+    }
+
+    /// Invert the levels of the data line, including the idle level.
+    ///
+    /// This is needed when the strip is driven through an inverting level
+    /// shifter, e.g. a single transistor. The pulse timing is not affected,
+    /// and [IdleOutput] keeps referring to the level seen by the LEDs.
+    pub const fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Whether the levels of the data line are inverted.
+    pub const fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Set the number of RMT RAM blocks used by the channel.
+    ///
+    /// More blocks let [SmartLedsAdapterAsync] send more LEDs per RMT
+    /// transaction, so there are fewer gaps on the wire, and reduce how often
+    /// the blocking adapters have to refill the RAM during a transmission. The
+    /// additional blocks are taken from the following channels, which can't be
+    /// used at the same time. Values are clamped to between 1 and the number
+    /// of blocks of the chip. Defaults to 1.
+    pub const fn with_memsize(mut self, memsize: u8) -> Self {
+        self.memsize = if memsize == 0 {
+            1
+        } else if memsize > RMT_BLOCKS {
+            RMT_BLOCKS
+        } else {
+            memsize
+        };
+        self
+    }
+
+    /// Number of RMT RAM blocks used by the channel.
+    pub const fn memsize(&self) -> u8 {
+        self.memsize
+    }
+
+    /// Keep re-sending the last frame with the loop mode of the RMT until the
+    /// next write, for chips such as the TM1814 that blank the LEDs if they
+    /// aren't refreshed regularly.
+    ///
+    /// The loop mode can't refill the RAM of the channel, so the whole frame
+    /// including the end delimiter has to fit into it: a frame of `n` LEDs
+    /// needs `buffer_size(n)` pulse codes, see [buffer_size], and every block
+    /// set with [AdapterConfig::with_memsize] holds 64 of them on the ESP32
+    /// and ESP32-S2 and 48 on the other chips. With the default of one block
+    /// that is a single RGB LED (two on the ESP32 and ESP32-S2), so the memory
+    /// size usually has to be raised. Longer frames are rejected with
+    /// [LedAdapterError::FrameTooLongToRepeat].
+    ///
+    /// Supported by [SmartLedsAdapter] (except for
+    /// [SmartLedsAdapter::write_timeout]), [Tm1814Adapter],
+    /// [SmartLedsAdapterSlice] and its aliases, [SmartLedsAdapterCustom] and
+    /// [SmartLedsAdapterRgbw]. It is ignored by [SmartLedsAdapterAsync],
+    /// [SmartLedsAdapterStreaming], [SmartLedsAdapterQueued] and
+    /// [MultiStripAdapter], which always send every frame once.
+    pub const fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.auto_refresh = auto_refresh;
+        self
+    }
+
+    /// Whether the last frame is re-sent until the next write.
+    pub const fn auto_refresh(&self) -> bool {
+        self.auto_refresh
+    }
+
+    /// Apply a gamma correction with the given gamma value to every color
+    /// channel while encoding, or disable it with `None`.
+    ///
+    /// LEDs respond linearly to the channel values while the eye doesn't, so
+    /// without a correction low brightness levels look washed out. A gamma of
+    /// about 2.2 to 2.8 suits most LEDs. Values are clamped to between 0.004
+    /// and 255. This replaces the curve of [AdapterConfig::with_cie1931].
+    /// Defaults to `None`.
+    pub const fn with_gamma(mut self, gamma: Option<f32>) -> Self {
+        self.cie1931 = false;
+        self.gamma = match gamma {
+            Some(gamma) => gamma_to_fixed(gamma),
+            None => 0,
+        };
+        self.gamma_table = gamma_table(self.gamma);
+        self
+    }
+
+    /// The gamma value of the correction, if enabled.
+    pub const fn gamma(&self) -> Option<f32> {
+        match self.gamma {
+            0 => None,
+            gamma => Some(gamma as f32 / 256.0),
+        }
+    }
+
+    /// Map every color channel through the CIE 1931 lightness curve while
+    /// encoding, which makes fades look perceptually linear. This replaces
+    /// the correction of [AdapterConfig::with_gamma]. Defaults to `false`.
+    pub const fn with_cie1931(mut self, cie1931: bool) -> Self {
+        self.cie1931 = cie1931;
+        self.gamma = 0;
+        self.gamma_table = if cie1931 {
+            cie1931_table()
+        } else {
+            gamma_table(0)
+        };
+        self
+    }
+
+    /// Whether the CIE 1931 lightness curve is applied.
+    pub const fn cie1931(&self) -> bool {
+        self.cie1931
+    }
+
+    /// Map the red, green and blue channels through the respective lookup
+    /// table while encoding, e.g. with curves measured with a colorimeter.
+    ///
+    /// The tables take the place of [AdapterConfig::with_gamma] and
+    /// [AdapterConfig::with_cie1931] for these channels, which still apply to
+    /// the white channel of RGBW LEDs. Defaults to `None`.
+    pub const fn with_transfer_tables(
+        mut self,
+        transfer_tables: Option<&'static [[u8; 256]; 3]>,
+    ) -> Self {
+        self.transfer_tables = transfer_tables;
+        self
+    }
+
+    /// The lookup tables of the red, green and blue channels, if set.
+    pub const fn transfer_tables(&self) -> Option<&'static [[u8; 256]; 3]> {
+        self.transfer_tables
+    }
+
+    /// Scale every color channel by `brightness / 256` while encoding, after
+    /// the gamma correction. Defaults to 255, i.e. full brightness.
+    pub const fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// The brightness every color channel is scaled with.
+    pub const fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Scale the red, green and blue channels by the respective value of
+    /// `correction / 256` while encoding, e.g. to compensate for a color tint
+    /// of the LEDs. The white channel of RGBW LEDs isn't affected. See
+    /// [ColorCorrection] for typical values. Defaults to white, i.e. no
+    /// correction.
+    pub const fn with_correction(mut self, correction: RGB8) -> Self {
+        self.correction = [correction.r, correction.g, correction.b];
+        self.with_white_point()
+    }
+
+    /// The factors the red, green and blue channels are scaled with.
+    pub const fn correction(&self) -> RGB8 {
+        let [r, g, b] = self.correction;
+        RGB8 { r, g, b }
+    }
 
-const SK68XX_CODE_PERIOD: u32 = 1250; // 800kHz
-const SK68XX_T0H_NS: u32 = 400; // 300ns per SK6812 datasheet, 400 per WS2812. Some require >350ns for T0H. Others <500ns for T0H.
-const SK68XX_T0L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T0H_NS;
-const SK68XX_T1H_NS: u32 = 850; // 900ns per SK6812 datasheet, 850 per WS2812. > 550ns is sometimes enough. Some require T1H >= 2 * T0H. Some require > 300ns T1L.
-const SK68XX_T1L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T1H_NS;
+    /// Tint all colors to the color of a light source, on top of the
+    /// correction of [AdapterConfig::with_correction]. Defaults to
+    /// [ColorTemperature::Uncorrected].
+    pub const fn with_color_temperature(mut self, color_temperature: ColorTemperature) -> Self {
+        self.color_temperature = color_temperature;
+        self.with_white_point()
+    }
+
+    /// The color temperature all colors are tinted to.
+    pub const fn color_temperature(&self) -> ColorTemperature {
+        self.color_temperature
+    }
+
+    /// Scale down the brightness of frames whose estimated current would
+    /// exceed the budget, e.g. to prevent brown-outs of USB-powered strips
+    /// when an effect goes full white. The current is estimated from the
+    /// colors of the frame, see [LedEncoder::load].
+    ///
+    /// Writes can only iterate over the colors once, while encoding them, so
+    /// the brightness is lowered from the frame after the one exceeding the
+    /// budget. [SmartLedsAdapter::write_slice], [SmartLedsAdapter::fill] and
+    /// the encoders of frames sent later limit the frame itself.
+    /// Defaults to `None`.
+    pub const fn with_power_limit(mut self, power_limit: Option<PowerLimit>) -> Self {
+        self.power_limit = power_limit;
+        self
+    }
+
+    /// The power budget of the strip, if limited.
+    pub const fn power_limit(&self) -> Option<PowerLimit> {
+        self.power_limit
+    }
+
+    // Combines the correction and color temperature into the factors applied
+    // while encoding.
+    const fn with_white_point(mut self) -> Self {
+        let temperature = self.color_temperature.rgb();
+        let [r, g, b] = self.correction;
+        self.white_point = [
+            scale(r, temperature.r),
+            scale(g, temperature.g),
+            scale(b, temperature.b),
+        ];
+        self
+    }
+}
+
+/// Converts a gamma value to an 8.8 fixed point number, clamped to between
+/// 1/256 and 255.
+const fn gamma_to_fixed(gamma: f32) -> u16 {
+    if gamma * 256.0 >= 1.0 {
+        // The cast saturates, which clamps the upper end
+        (gamma * 256.0) as u16
+    } else {
+        1
+    }
+}
+
+/// Lookup table mapping channel values to `value ^ gamma`, with `gamma` as an
+/// 8.8 fixed point number. A gamma of 0 gives the identity.
+const fn gamma_table(gamma: u16) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut value = 0;
+    while value < 256 {
+        table[value] = if gamma == 0 {
+            value as u8
+        } else {
+            // The value in [0, 1] as a 16.16 fixed point number
+            let x = (value as u64) * 65536 / 255;
+
+            // The integer part of the exponent by multiplication
+            let mut result = 65536;
+            let mut i = 0;
+            while i < gamma >> 8 {
+                result = (result * x) >> 16;
+                i += 1;
+            }
+
+            // The fractional part from x^(1/2), x^(1/4), ...
+            let mut root = x;
+            let mut bit = 0;
+            while bit < 8 {
+                root = sqrt_q16(root);
+                if gamma & (0x80 >> bit) != 0 {
+                    result = (result * root) >> 16;
+                }
+                bit += 1;
+            }
+
+            ((result * 255 + 32768) >> 16) as u8
+        };
+        value += 1;
+    }
+    table
+}
+
+/// Lookup table mapping channel values, taken as the lightness L*, to the
+/// luminance according to CIE 1931.
+const fn cie1931_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut value = 0;
+    while value < 256 {
+        // With L* = value / 255 * 100, the luminance is L* / 903.3 up to a
+        // L* of 8 and ((L* + 16) / 116)^3 above, both scaled to 0..=255.
+        let lightness = value as u64 * 100;
+        table[value] = if lightness <= 8 * 255 {
+            ((lightness * 10 + 9033 / 2) / 9033) as u8
+        } else {
+            let n = lightness + 16 * 255;
+            let d = 116 * 255;
+            ((n * n * n * 255 + d * d * d / 2) / (d * d * d)) as u8
+        };
+        value += 1;
+    }
+    table
+}
+
+/// Square root of a 16.16 fixed point number no larger than 1.
+const fn sqrt_q16(x: u64) -> u64 {
+    // sqrt(x / 2^16) * 2^16 = sqrt(x * 2^16)
+    let mut n = x << 16;
+    let mut root = 0;
+    let mut bit = 1 << 32;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if n >= root + bit {
+            n -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+fn led_config(config: &AdapterConfig) -> TxChannelConfig {
+    let (_, low) = pulse_levels(config.inverted);
+    let (idle_output, idle_level) = match config.idle_output {
+        IdleOutput::Low => (true, low),
+        IdleOutput::High => (true, !low),
+        IdleOutput::HoldLast => (false, low),
+    };
+
+    TxChannelConfig::default()
+        .with_clk_divider(config.clock_divider)
+        .with_idle_output_level(idle_level)
+        .with_carrier_modulation(false)
+        .with_idle_output(idle_output)
+        .with_memsize(config.memsize)
+}
+
+fn configure_channel<'d, Dm, C, O>(
+    channel: C,
+    pin: O,
+    config: &AdapterConfig,
+) -> Result<Channel<Dm, C::Raw>, RmtError>
+where
+    Dm: DriverMode,
+    O: PeripheralOutput<'d>,
+    C: TxChannelCreator<'d, Dm>,
+{
+    let pin = pin.into();
+    // The RMT driver sets the pin up as a push-pull output with the default
+    // output configuration, so we keep a handle to switch the drive mode after
+    // the channel has been configured.
+    //
+    // SAFETY: The clone is only used to apply the output configuration right
+    // below and dropped before returning, it is never connected to another
+    // peripheral. The RMT driver only routes its signal to the pin and doesn't
+    // depend on the drive mode, so changing it behind the back of the driver
+    // doesn't break any of its assumptions.
+    let output = config.open_drain.then(|| unsafe { pin.clone_unchecked() });
+
+    let channel = channel.configure_tx(pin, led_config(config))?;
+
+    if let Some(output) = output {
+        // Same as what the RMT driver applied, except for the drive mode
+        output.apply_output_config(&OutputConfig::default().with_drive_mode(DriveMode::OpenDrain));
+    }
+
+    Ok(channel)
+}
+
+/// Transmission of a frame by a blocking RMT channel.
+type BlockingTransaction<'a, TX> = SingleShotTxTransaction<'a, Channel<Blocking, TX>, u32>;
+
+/// State of the RMT channel of the blocking adapter.
+enum ChannelState<TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// The adapter hasn't been initialized yet.
+    Uninitialized,
+    /// The channel is ready to transmit.
+    Ready(Channel<Blocking, TX>),
+    /// The channel keeps repeating the last frame.
+    Looping(ContinuousTxTransaction<Channel<Blocking, TX>>),
+    /// The channel was lost in a failed transmission.
+    Lost,
+}
+
+impl<TX> ChannelState<TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Takes the channel out for a transmission, stopping the repetition of
+    /// the last frame at its end.
+    fn take(&mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        match core::mem::replace(self, ChannelState::Lost) {
+            ChannelState::Ready(channel) => Ok(channel),
+            // An error in a repetition doesn't affect the next frame
+            ChannelState::Looping(transaction) => match transaction.stop_next() {
+                Ok(channel) | Err((_, channel)) => Ok(channel),
+            },
+            ChannelState::Uninitialized => {
+                *self = ChannelState::Uninitialized;
+                Err(LedAdapterError::NotInitialized)
+            }
+            ChannelState::Lost => Err(LedAdapterError::ChannelUnavailable),
+        }
+    }
+
+    /// Transmits the buffer and waits for the transmission to finish.
+    fn transmit(&mut self, buffer: &[u32]) -> Result<(), LedAdapterError> {
+        // Perform the actual RMT operation. We use the u32 values here right away.
+        let channel = self.take()?;
+        match channel.transmit(buffer) {
+            Ok(transaction) => match transaction.wait() {
+                Ok(chan) => {
+                    *self = ChannelState::Ready(chan);
+                    Ok(())
+                }
+                Err((e, chan)) => {
+                    *self = ChannelState::Ready(chan);
+                    Err(LedAdapterError::TransmissionError(e))
+                }
+            },
+            Err(e) => Err(LedAdapterError::TransmissionError(e)),
+        }
+    }
+
+    /// Transmits the buffer and reports [LedAdapterError::Timeout] if the
+    /// transmission takes longer than `timeout`.
+    ///
+    /// The transaction can't be aborted and only refills the RAM of the
+    /// channel while it is polled, so it is polled until it is done even
+    /// after the timeout, which gives the channel back.
+    fn transmit_timeout(
+        &mut self,
+        buffer: &[u32],
+        timeout: Duration,
+    ) -> Result<(), LedAdapterError> {
+        let start = Instant::now();
+        let channel = self.take()?;
+        let mut transaction = channel.transmit(buffer)?;
+        while !transaction.poll() {}
+        let timed_out = start.elapsed() > timeout;
+
+        match transaction.wait() {
+            Ok(chan) => {
+                *self = ChannelState::Ready(chan);
+                if timed_out {
+                    return Err(LedAdapterError::Timeout);
+                }
+                Ok(())
+            }
+            Err((e, chan)) => {
+                *self = ChannelState::Ready(chan);
+                Err(LedAdapterError::TransmissionError(e))
+            }
+        }
+    }
+
+    /// Transmits the buffer and keeps repeating it until the next
+    /// transmission if `repeat` is set, which requires it to fit into `ram`
+    /// pulse codes.
+    fn transmit_repeating(
+        &mut self,
+        buffer: &[u32],
+        repeat: bool,
+        ram: usize,
+    ) -> Result<(), LedAdapterError> {
+        if !repeat {
+            return self.transmit(buffer);
+        }
+        if buffer.len() > ram {
+            return Err(LedAdapterError::FrameTooLongToRepeat {
+                len: buffer.len(),
+                ram,
+            });
+        }
+
+        let channel = self.take()?;
+        match channel.transmit_continuously(buffer) {
+            Ok(transaction) => {
+                *self = ChannelState::Looping(transaction);
+                Ok(())
+            }
+            Err(e) => Err(LedAdapterError::TransmissionError(e)),
+        }
+    }
+
+    /// Sends an encoded frame once the frame gap of the configuration has
+    /// passed since `last_frame_end`, which is then updated, and logs
+    /// failures.
+    ///
+    /// Waits at most `timeout` for the transmission if given, otherwise the
+    /// frame is repeated if [AdapterConfig::with_auto_refresh] is set.
+    fn send_frame(
+        &mut self,
+        frame: &[u32],
+        config: &AdapterConfig,
+        timeout: Option<Duration>,
+        last_frame_end: &mut Option<Instant>,
+    ) -> Result<(), LedAdapterError> {
+        wait_for_frame_gap(*last_frame_end, config.frame_gap_ns());
+        let result = match timeout {
+            Some(timeout) => self.transmit_timeout(frame, timeout),
+            None => self.transmit_repeating(
+                frame,
+                config.auto_refresh(),
+                config.memsize() as usize * RMT_BLOCK_SIZE,
+            ),
+        };
+        *last_frame_end = Some(Instant::now());
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+
+    /// Starts sending an encoded frame like [ChannelState::send_frame], but
+    /// returns the transaction instead of waiting for it to finish.
+    ///
+    /// Frames repeated with [AdapterConfig::with_auto_refresh] don't need to
+    /// be waited for, so `None` is returned for them.
+    fn start_frame<'a>(
+        &mut self,
+        frame: &'a [u32],
+        config: &AdapterConfig,
+        last_frame_end: &mut Option<Instant>,
+    ) -> Result<Option<BlockingTransaction<'a, TX>>, LedAdapterError> {
+        wait_for_frame_gap(*last_frame_end, config.frame_gap_ns());
+        let result = if config.auto_refresh() {
+            let result = self
+                .transmit_repeating(frame, true, config.memsize() as usize * RMT_BLOCK_SIZE)
+                .map(|()| None);
+            *last_frame_end = Some(Instant::now());
+            result
+        } else {
+            self.take()
+                .and_then(|channel| Ok(Some(channel.transmit(frame)?)))
+        };
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+
+    fn as_mut(&mut self) -> Result<&mut Channel<Blocking, TX>, LedAdapterError> {
+        if let ChannelState::Looping(_) = self {
+            let channel = self.take()?;
+            *self = ChannelState::Ready(channel);
+        }
+
+        match self {
+            ChannelState::Ready(channel) => Ok(channel),
+            ChannelState::Looping(_) | ChannelState::Lost => {
+                Err(LedAdapterError::ChannelUnavailable)
+            }
+            ChannelState::Uninitialized => Err(LedAdapterError::NotInitialized),
+        }
+    }
+}
+
+// The color channels in the order they are sent.
+fn ordered_channels(value: RGB8, config: &AdapterConfig) -> [u8; 3] {
+    let [r, g, b] = config.white_point;
+    let [table_r, table_g, table_b] = transfer_tables(config);
+    let mut channels = config.color_order.order([
+        scale(transfer(value.r, table_r, config), r),
+        scale(transfer(value.g, table_g, config), g),
+        scale(transfer(value.b, table_b, config), b),
+    ]);
+    if config.clamp_first_channel {
+        channels[0] = channels[0].min(0xfe);
+    }
+    channels
+}
+
+// The color channels of a color with 16 bits per channel in the order they
+// are sent, corrected like `ordered_channels` but in 8.8 fixed point instead
+// of rounded down to 8 bits. The first channel isn't clamped.
+fn ordered_channels_fine(value: RGB16, config: &AdapterConfig) -> [u16; 3] {
+    let [r, g, b] = config.white_point;
+    let [table_r, table_g, table_b] = transfer_tables(config);
+    let channel = |value, table, white| {
+        scale_fine(
+            scale_fine(interpolate(value, table), config.brightness),
+            white,
+        )
+    };
+    config.color_order.order([
+        channel(value.r, table_r, r),
+        channel(value.g, table_g, g),
+        channel(value.b, table_b, b),
+    ])
+}
+
+// The tables the red, green and blue channels are mapped through.
+fn transfer_tables(config: &AdapterConfig) -> [&[u8; 256]; 3] {
+    match config.transfer_tables {
+        Some([r, g, b]) => [r, g, b],
+        None => [&config.gamma_table; 3],
+    }
+}
+
+// Sum of the channel values sent for an RGB color, see `LedEncoder::load`.
+fn rgb_load(color: RGB8, config: &AdapterConfig) -> u32 {
+    ordered_channels(color, config)
+        .into_iter()
+        .map(u32::from)
+        .sum()
+}
+
+/// Factor the power limit scales the brightness of a frame with, see
+/// [AdapterConfig::with_power_limit].
+///
+/// The factor depends on all colors of the frame, but most writes can only
+/// iterate over them once, while encoding. They encode with the factor of the
+/// previous frame and keep the one of this frame for the next, so a frame
+/// exceeding the limit is only scaled down one frame late.
+#[derive(Debug, Clone, Copy)]
+struct PowerScale(u8);
+
+impl PowerScale {
+    /// Leaves the brightness as it is.
+    const NONE: Self = Self(255);
+
+    /// The factor for a frame of `led_count` LEDs whose channel values add up
+    /// to `load` with `config`, scaling the brightness down if the estimated
+    /// current exceeds the power limit.
+    fn of(config: &AdapterConfig, led_count: usize, load: u32) -> Self {
+        let Some(power_limit) = config.power_limit else {
+            return Self::NONE;
+        };
+
+        let idle_current = (led_count as u32).saturating_mul(power_limit.idle_current);
+        let channel_current = load.saturating_mul(power_limit.channel_current) / 255;
+        if channel_current.saturating_add(idle_current) <= power_limit.max_current
+            || channel_current == 0
+        {
+            return Self::NONE;
+        }
+
+        // Only the current of the channels scales with the brightness
+        let budget = power_limit.max_current.saturating_sub(idle_current);
+        Self((budget as u64 * 256 / channel_current as u64).saturating_sub(1) as u8)
+    }
+
+    /// The factor for the colors, from a pass over them before they are
+    /// encoded.
+    fn of_colors<E: LedEncoder>(
+        encoder: &E,
+        colors: impl Iterator<Item = E::Color>,
+        config: &AdapterConfig,
+    ) -> Self {
+        if config.power_limit.is_none() {
+            return Self::NONE;
+        }
+
+        let (led_count, load) = colors.fold((0, 0u32), |(led_count, load), color| {
+            (
+                led_count + 1,
+                load.saturating_add(encoder.load(&color, config)),
+            )
+        });
+        Self::of(config, led_count, load)
+    }
+
+    /// The configuration to encode with, with the brightness scaled.
+    fn apply(self, config: &AdapterConfig) -> AdapterConfig {
+        config.with_brightness(scale(config.brightness, self.0))
+    }
+}
+
+// Rounds a color with 16 bits per channel to the nearest 8-bit color.
+fn round_rgb16(color: RGB16) -> RGB8 {
+    let round = |channel: u16| ((channel as u32 + 0x80) >> 8).min(255) as u8;
+    RGB8 {
+        r: round(color.r),
+        g: round(color.g),
+        b: round(color.b),
+    }
+}
+
+// Applies the gamma correction and brightness of the configuration to a
+// channel value.
+fn correct_channel(value: u8, config: &AdapterConfig) -> u8 {
+    transfer(value, &config.gamma_table, config)
+}
+
+// Maps a channel value through the table and applies the brightness.
+fn transfer(value: u8, table: &[u8; 256], config: &AdapterConfig) -> u8 {
+    scale(table[value as usize], config.brightness)
+}
+
+// Scales the value by `factor / 256`, the same way as `smart_leds::brightness`.
+const fn scale(value: u8, factor: u8) -> u8 {
+    ((value as u16 * (factor as u16 + 1)) >> 8) as u8
+}
+
+// Scales the value in 8.8 fixed point by `factor / 256`, like `scale`.
+const fn scale_fine(value: u16, factor: u8) -> u16 {
+    ((value as u32 * (factor as u32 + 1)) >> 8) as u16
+}
+
+// Maps a 16-bit value through the table, interpolating between its entries,
+// and returns the result in 8.8 fixed point.
+fn interpolate(value: u16, table: &[u8; 256]) -> u16 {
+    let (index, fraction) = ((value >> 8) as usize, (value & 0xff) as u32);
+    let low = table[index] as u32;
+    let high = table[(index + 1).min(255)] as u32;
+    (low * (256 - fraction) + high * fraction) as u16
+}
+
+/// Precomputed pulses for every possible channel value, which saves the
+/// branching per bit when encoding.
+#[cfg(feature = "lut")]
+struct PulseLut([[u32; 8]; 256]);
+
+#[cfg(feature = "lut")]
+impl PulseLut {
+    const EMPTY: PulseLut = PulseLut([[0; 8]; 256]);
+
+    fn new(pulses: BitPulses) -> Self {
+        let mut lut = Self::EMPTY;
+        for (value, entry) in lut.0.iter_mut().enumerate() {
+            pulses.write_bits(value as u32, entry);
+        }
+        lut
+    }
+}
+
+/// Encoder of RGB colors copying the pulses of every channel from a
+/// [PulseLut], which has to match the pulses passed to it.
+#[cfg(feature = "lut")]
+struct LutEncoder<'a>(&'a PulseLut);
+
+#[cfg(feature = "lut")]
+impl LedEncoder for LutEncoder<'_> {
+    type Color = RGB8;
+    const PULSES_PER_LED: usize = RMT_RAM_ONE_LED;
+
+    fn encode(&mut self, color: RGB8, _: &BitPulses, config: &AdapterConfig, out: &mut [u32]) {
+        for (channel, out) in ordered_channels(color, config)
+            .into_iter()
+            .zip(out.chunks_exact_mut(8))
+        {
+            out.copy_from_slice(&self.0 .0[channel as usize]);
+        }
+    }
+
+    fn load(&self, color: &RGB8, config: &AdapterConfig) -> u32 {
+        rgb_load(*color, config)
+    }
+}
+
+/// Encoder of RGB colors sending the `BITS` most significant bits of every
+/// channel, like [Rgb8Encoder] does for all 8.
+struct RgbBitsEncoder<const BITS: usize>;
+
+impl<const BITS: usize> LedEncoder for RgbBitsEncoder<BITS> {
+    type Color = RGB8;
+    const PULSES_PER_LED: usize = rmt_ram_one_led(BITS);
+
+    fn encode(&mut self, color: RGB8, pulses: &BitPulses, config: &AdapterConfig, out: &mut [u32]) {
+        pulses.write_channels::<BITS>(ordered_channels(color, config), out);
+    }
+
+    fn load(&self, color: &RGB8, config: &AdapterConfig) -> u32 {
+        rgb_load(*color, config)
+    }
+}
+
+/// Busy-polls the future until it completes, without an executor.
+fn block_on<F: Future>(future: F) -> F::Output {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+    const RAW: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
+
+    // SAFETY: The waker does nothing, so it trivially upholds the contract of
+    // `RawWakerVTable`.
+    let waker = unsafe { Waker::from_raw(RAW) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Waker calling `on_sent` when woken, to be notified by the RMT interrupt
+/// once a transaction of the asynchronous driver is done.
+fn interrupt_waker(on_sent: fn()) -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, |_| {});
+
+    fn clone(data: *const ()) -> RawWaker {
+        RawWaker::new(data, &VTABLE)
+    }
+
+    fn wake(data: *const ()) {
+        // SAFETY: The data of the waker is always the `fn()` it was created
+        // from, see below.
+        let on_sent = unsafe { core::mem::transmute::<*const (), fn()>(data) };
+        on_sent();
+    }
+
+    // SAFETY: The data is a function pointer, which is `Send + Sync` and valid
+    // forever, and the functions of the vtable don't access anything else.
+    unsafe { Waker::from_raw(RawWaker::new(on_sent as *const (), &VTABLE)) }
+}
+
+/// Yields to the executor until `deadline` has passed.
+async fn yield_until(deadline: Instant) {
+    poll_fn(|cx| {
+        if Instant::now() >= deadline {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// Busy-waits until at least `min_gap_ns` have passed since the end of the
+/// last frame.
+fn wait_for_frame_gap(last_frame_end: Option<Instant>, min_gap_ns: u32) {
+    Delay::new().delay_nanos(frame_gap_remaining_ns(last_frame_end, min_gap_ns));
+}
+
+/// Waits until at least `min_gap_ns` have passed since the end of the last
+/// frame, yielding to the executor while whole microseconds are left and
+/// only busy-waiting for the rest.
+async fn frame_gap(last_frame_end: Option<Instant>, min_gap_ns: u32) {
+    poll_fn(|cx| {
+        if frame_gap_remaining_ns(last_frame_end, min_gap_ns) < 1000 {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await;
+    wait_for_frame_gap(last_frame_end, min_gap_ns);
+}
+
+/// Whether at least `min_gap_ns` have passed since the end of the last frame.
+fn frame_gap_elapsed(last_frame_end: Option<Instant>, min_gap_ns: u32) -> bool {
+    frame_gap_remaining_ns(last_frame_end, min_gap_ns) == 0
+}
+
+/// Nanoseconds left until `min_gap_ns` have passed since the end of the last
+/// frame.
+fn frame_gap_remaining_ns(last_frame_end: Option<Instant>, min_gap_ns: u32) -> u32 {
+    match last_frame_end {
+        Some(end) if min_gap_ns > 0 => {
+            let elapsed_ns = end.elapsed().as_micros().saturating_mul(1000);
+            (min_gap_ns as u64).saturating_sub(elapsed_ns) as u32
+        }
+        _ => 0,
+    }
+}
+
+/// Statistics about the frames sent by an adapter.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameStats {
+    /// Time in microseconds spent encoding the colors of the last frame.
+    pub last_encode_us: u64,
+    /// Time in microseconds spent transmitting the last frame, excluding the
+    /// encoding.
+    pub last_frame_us: u64,
+    /// Number of LEDs in the last frame.
+    pub last_led_count: usize,
+    /// Number of frames transmitted successfully.
+    pub frames_sent: u32,
+    /// Number of frames that failed to encode or transmit.
+    pub errors: u32,
+}
+
+#[cfg(feature = "stats")]
+impl FrameStats {
+    const fn new() -> Self {
+        Self {
+            last_encode_us: 0,
+            last_frame_us: 0,
+            last_led_count: 0,
+            frames_sent: 0,
+            errors: 0,
+        }
+    }
+
+    fn record_encode(&mut self, start: Instant, ok: bool) {
+        self.last_encode_us = start.elapsed().as_micros();
+        if !ok {
+            self.errors = self.errors.wrapping_add(1);
+        }
+    }
 
-/// All types of errors that can happen during the conversion and transmission
-/// of LED commands
-#[derive(Debug)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub enum LedAdapterError {
-    /// Raised in the event that the provided data container is not large enough
-    BufferSizeExceeded,
-    /// Raised if something goes wrong in the transmission,
-    TransmissionError(RmtError),
+    fn record_transmit(&mut self, start: Instant, led_count: usize, ok: bool) {
+        self.last_frame_us = start.elapsed().as_micros();
+        self.last_led_count = led_count;
+        if ok {
+            self.frames_sent = self.frames_sent.wrapping_add(1);
+        } else {
+            self.errors = self.errors.wrapping_add(1);
+        }
+    }
 }
 
-impl From<RmtError> for LedAdapterError {
-    fn from(e: RmtError) -> Self {
-        LedAdapterError::TransmissionError(e)
+/// Check whether `len` LEDs fit into a buffer for `capacity` LEDs.
+const fn check_len(len: usize, capacity: usize) -> Result<(), LedAdapterError> {
+    if len > capacity {
+        Err(LedAdapterError::BufferSizeExceeded {
+            needed: len,
+            capacity,
+            encoded: 0,
+        })
+    } else {
+        Ok(())
     }
 }
 
-fn led_pulses_for_clock(src_clock: u32) -> (u32, u32) {
-    (
-        PulseCode::new(
-            Level::High,
-            ((SK68XX_T0H_NS * src_clock) / 1000) as u16,
-            Level::Low,
-            ((SK68XX_T0L_NS * src_clock) / 1000) as u16,
-        ),
-        PulseCode::new(
-            Level::High,
-            ((SK68XX_T1H_NS * src_clock) / 1000) as u16,
-            Level::Low,
-            ((SK68XX_T1L_NS * src_clock) / 1000) as u16,
-        ),
-    )
+/// Encodes all colors of the iterator with the encoder followed by the end
+/// delimiter into `buffer` and returns the number of LEDs encoded.
+///
+/// The colors are encoded with `power` applied, which is then updated for
+/// the next frame, see [PowerScale].
+fn encode_frame<E: LedEncoder, I: Into<E::Color>>(
+    encoder: &mut E,
+    buffer: &mut [u32],
+    iterator: impl IntoIterator<Item = I>,
+    pulses: &BitPulses,
+    config: &AdapterConfig,
+    power: &mut PowerScale,
+    reset_pulse: u32,
+) -> Result<usize, LedAdapterError> {
+    let led_size = E::PULSES_PER_LED;
+    let capacity = buffer.len().saturating_sub(1) / led_size;
+    let led_count = encode_leds(
+        encoder,
+        buffer,
+        iterator,
+        capacity,
+        |led| led * led_size,
+        pulses,
+        config,
+        power,
+    )?;
+
+    // Finally, add an end element. The capacity leaves a slot for it, unless
+    // the buffer is empty.
+    *buffer
+        .get_mut(led_count * led_size)
+        .ok_or(LedAdapterError::DelimiterSlotMissing)? = reset_pulse;
+
+    Ok(led_count)
 }
 
-fn led_config() -> TxChannelConfig {
-    TxChannelConfig::default()
-        .with_clk_divider(1)
-        .with_idle_output_level(Level::Low)
-        .with_carrier_modulation(false)
-        .with_idle_output(true)
-}
-
-fn convert_rgb_to_pulses(
-    value: RGB8,
-    mut_iter: &mut IterMut<u32>,
-    pulses: (u32, u32),
-) -> Result<(), LedAdapterError> {
-    convert_rgb_channel_to_pulses(value.g, mut_iter, pulses)?;
-    convert_rgb_channel_to_pulses(value.r, mut_iter, pulses)?;
-    convert_rgb_channel_to_pulses(value.b, mut_iter, pulses)?;
-    Ok(())
-}
-
-fn convert_rgb_channel_to_pulses(
-    channel_value: u8,
-    mut_iter: &mut IterMut<u32>,
-    pulses: (u32, u32),
-) -> Result<(), LedAdapterError> {
-    for position in [128, 64, 32, 16, 8, 4, 2, 1] {
-        *mut_iter.next().ok_or(LedAdapterError::BufferSizeExceeded)? =
-            match channel_value & position {
-                0 => pulses.0,
-                _ => pulses.1,
-            }
+/// Encodes the colors of the iterator with the encoder, each LED into the
+/// pulse codes starting at `offset(index)`, and returns the number of LEDs
+/// encoded. The end delimiters are left to the caller.
+///
+/// The colors are encoded with `power` applied, and once all of them are,
+/// `power` is updated to the factor of this frame for the next one.
+///
+/// This will result in an `BufferSizeExceeded` error in case the iterator
+/// provides more than `capacity` elements or they don't fit into the buffer.
+#[allow(clippy::too_many_arguments)]
+fn encode_leds<E: LedEncoder, I: Into<E::Color>>(
+    encoder: &mut E,
+    buffer: &mut [u32],
+    iterator: impl IntoIterator<Item = I>,
+    capacity: usize,
+    offset: impl Fn(usize) -> usize,
+    pulses: &BitPulses,
+    config: &AdapterConfig,
+    power: &mut PowerScale,
+) -> Result<usize, LedAdapterError> {
+    encoder.start_frame();
+
+    let limited = power.apply(config);
+    let mut led_count = 0;
+    let mut load = 0u32;
+    for item in iterator {
+        let start = offset(led_count);
+        let out = buffer
+            .get_mut(start..start + E::PULSES_PER_LED)
+            .filter(|_| led_count < capacity)
+            .ok_or(LedAdapterError::BufferSizeExceeded {
+                needed: led_count + 1,
+                capacity,
+                encoded: led_count,
+            })?;
+        let color = item.into();
+        if config.power_limit.is_some() {
+            load = load.saturating_add(encoder.load(&color, config));
+        }
+        encoder.encode(color, pulses, &limited, out);
+        led_count += 1;
     }
+    *power = PowerScale::of(config, led_count, load);
 
-    Ok(())
+    Ok(led_count)
 }
 
 /// Function to calculate the required RMT buffer size for a given number of LEDs when using
@@ -136,21 +1852,42 @@ fn convert_rgb_channel_to_pulses(
 ///
 /// This buffer size is calculated for the synchronous API provided by the [SmartLedsAdapter].
 /// [buffer_size_async] should be used for the asynchronous API.
+///
+/// The two helpers use different formulas and are not interchangeable: the
+/// blocking adapter sends all LEDs in a single transaction followed by one end
+/// delimiter, while the asynchronous adapter splits the frame into
+/// transactions that fit into the RAM of the channel, each ending with its own
+/// delimiter.
 pub const fn buffer_size(num_leds: usize) -> usize {
+    buffer_size_with_bit_depth(num_leds, 8)
+}
+
+/// Function to calculate the required RMT buffer size for a given number of LEDs with a
+/// non-default number of bits per color channel when using the blocking API.
+///
+/// See [SmartLedsAdapter::new_with_bit_depth].
+pub const fn buffer_size_with_bit_depth(num_leds: usize, bits_per_channel: usize) -> usize {
     // 1 additional pulse for the end delimiter
-    num_leds * RMT_RAM_ONE_LED + 1
+    num_leds * rmt_ram_one_led(bits_per_channel) + 1
 }
 
 /// Macro to allocate a buffer sized for a specific number of LEDs to be
 /// addressed.
 ///
 /// Attempting to use more LEDs that the buffer is configured for will result in
-/// an `LedAdapterError:BufferSizeExceeded` error.
+/// an `LedAdapterError::BufferSizeExceeded` error.
+///
+/// The adapters check at compile time that their buffer length matches the
+/// respective size helper, so a buffer of an arbitrary length is rejected when
+/// constructing the adapter.
 #[macro_export]
 macro_rules! smart_led_buffer {
     ( $num_leds: expr ) => {
         [0u32; $crate::buffer_size($num_leds)]
     };
+    ( $num_leds: expr, $bits_per_channel: expr ) => {
+        [0u32; $crate::buffer_size_with_bit_depth($num_leds, $bits_per_channel)]
+    };
 }
 
 /// Deprecated alias for [smart_led_buffer] macro.
@@ -164,13 +1901,72 @@ macro_rules! smartLedBuffer {
 
 /// Adapter taking an RMT channel and a specific pin and providing RGB LED
 /// interaction functionality using the `smart-leds` crate
-pub struct SmartLedsAdapter<TX, const BUFFER_SIZE: usize>
+///
+/// `BITS_PER_CHANNEL` is the number of bits sent for every color channel,
+/// which is 8 for all common LED types.
+///
+/// Frames that don't fit into the RAM of the channel are fed to it by the CPU
+/// while the transmission is running. The RMT driver of `esp-hal` doesn't
+/// support DMA, not even on the ESP32-S3, so the number of refills can only be
+/// reduced with [AdapterConfig::with_memsize].
+///
+/// # Sharing the adapter
+///
+/// The adapter is `Send` and has no lifetime, so it can be moved into a task
+/// or placed in a `static`, e.g. behind a `critical_section::Mutex` to share
+/// it with an interrupt handler. [SmartLedsAdapter::new_uninit] is `const`,
+/// which keeps the buffer out of the stack while doing so.
+///
+/// ```rust,ignore
+/// static LED: Mutex<RefCell<SmartLedsAdapter<ChannelRaw, BUFFER_SIZE>>> =
+///     Mutex::new(RefCell::new(SmartLedsAdapter::new_uninit([0; BUFFER_SIZE])));
+///
+/// critical_section::with(|cs| LED.borrow_ref_mut(cs).init(rmt.channel0, p.GPIO2))?;
+/// ```
+///
+/// The adapters stay `Send` whenever their RMT channel is:
+///
+/// ```rust,no_run
+/// # use esp_hal::{
+/// #     rmt::{
+/// #         Channel, ContinuousTxTransaction, RawChannelAccess, SingleShotTxTransaction,
+/// #         TxChannelInternal,
+/// #     },
+/// #     Async, Blocking,
+/// # };
+/// # use esp_hal_smartled::{SmartLedsAdapter, SmartLedsAdapterAsync, SmartLedsAdapterQueued};
+/// fn assert_send<T: Send>() {}
+///
+/// fn adapters_are_send<TX, Tx>()
+/// where
+///     TX: RawChannelAccess + TxChannelInternal + 'static,
+///     Tx: RawChannelAccess + TxChannelInternal + 'static,
+///     Channel<Blocking, TX>: Send,
+///     ContinuousTxTransaction<Channel<Blocking, TX>>: Send,
+///     SingleShotTxTransaction<'static, Channel<Blocking, TX>, u32>: Send,
+///     Channel<Async, Tx>: Send,
+/// {
+///     assert_send::<SmartLedsAdapter<TX, 25>>();
+///     assert_send::<SmartLedsAdapterAsync<Tx, 25>>();
+///     assert_send::<SmartLedsAdapterQueued<TX, 25, 2>>();
+/// }
+/// ```
+pub struct SmartLedsAdapter<TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize = 8>
 where
     TX: RawChannelAccess + TxChannelInternal + 'static,
 {
-    channel: Option<Channel<Blocking, TX>>,
+    channel: ChannelState<TX>,
     rmt_buffer: [u32; BUFFER_SIZE],
-    pulses: (u32, u32),
+    pulses: BitPulses,
+    #[cfg(feature = "lut")]
+    lut: PulseLut,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+    #[cfg(feature = "stats")]
+    stats: FrameStats,
 }
 
 impl<'d, TX, const BUFFER_SIZE: usize> SmartLedsAdapter<TX, BUFFER_SIZE>
@@ -178,6 +1974,10 @@ where
     TX: RawChannelAccess + TxChannelInternal + 'static,
 {
     /// Create a new adapter object that drives the pin using the RMT channel.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see [SmartLedsAdapter::try_new].
     pub fn new<C, O>(
         channel: C,
         pin: O,
@@ -187,20 +1987,696 @@ where
         O: PeripheralOutput<'d>,
         C: TxChannelCreator<'d, Blocking, Raw = TX>,
     {
-        let channel = channel.configure_tx(pin, led_config()).unwrap();
+        Self::new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// returning an error instead of panicking if the channel can't be
+    /// configured.
+    pub fn try_new<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+    ) -> Result<SmartLedsAdapter<TX, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapter::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> SmartLedsAdapter<TX, BUFFER_SIZE>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_bit_depth(channel, pin, rmt_buffer, config)
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, returning an error instead of panicking
+    /// if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<SmartLedsAdapter<TX, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_bit_depth(channel, pin, rmt_buffer, config)
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given pulse widths.
+    ///
+    /// # Panics
+    ///
+    /// If the pulse widths can't be sent at the tick rate of the default
+    /// configuration or the channel can't be configured, see
+    /// [SmartLedsAdapter::try_new_with_timing].
+    pub fn new_with_timing<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        timing: LedTiming,
+    ) -> SmartLedsAdapter<TX, BUFFER_SIZE>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_timing(channel, pin, rmt_buffer, timing).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given pulse widths, returning an error instead of panicking
+    /// if they can't be sent or the channel can't be configured.
+    pub fn try_new_with_timing<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        timing: LedTiming,
+    ) -> Result<SmartLedsAdapter<TX, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        let config = AdapterConfig::new().with_timing(Timing::Custom(timing));
+        Self::try_new_with_config(channel, pin, rmt_buffer, config)
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the configuration of the given chip.
+    ///
+    /// # Panics
+    ///
+    /// If the chip has a white channel, see [Chipset::channels], or the
+    /// channel can't be configured, see [SmartLedsAdapter::try_new_with_chipset].
+    pub fn new_with_chipset<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        chipset: Chipset,
+    ) -> SmartLedsAdapter<TX, BUFFER_SIZE>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_chipset(channel, pin, rmt_buffer, chipset).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the configuration of the given chip, returning an error instead of
+    /// panicking if the chip has a white channel or the channel can't be
+    /// configured.
+    pub fn try_new_with_chipset<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        chipset: Chipset,
+    ) -> Result<SmartLedsAdapter<TX, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        if chipset.channels() != 3 {
+            return Err(LedAdapterError::UnsupportedChipset(chipset));
+        }
+        Self::try_new_with_config(channel, pin, rmt_buffer, chipset.config())
+    }
+}
+
+impl<'d, TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize>
+    SmartLedsAdapter<TX, BUFFER_SIZE, BITS_PER_CHANNEL>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Number of LEDs the buffer can hold.
+    const CAPACITY: usize = BUFFER_SIZE.saturating_sub(1) / rmt_ram_one_led(BITS_PER_CHANNEL);
+
+    /// Create a new adapter object that sends `BITS_PER_CHANNEL` bits for
+    /// every color channel, for LED types with a bit depth other than 8.
+    ///
+    /// Only the most significant bits of each channel value are sent. The
+    /// buffer has to be sized with [buffer_size_with_bit_depth]. The
+    /// [SmartLedsAdapterAsync] always sends 8 bits.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapter::try_new_with_bit_depth].
+    pub fn new_with_bit_depth<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_bit_depth(channel, pin, rmt_buffer, config).unwrap()
+    }
+
+    /// Create a new adapter object that sends `BITS_PER_CHANNEL` bits for
+    /// every color channel, returning an error instead of panicking if the
+    /// channel can't be configured.
+    pub fn try_new_with_bit_depth<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        let mut adapter = Self::new_uninit(rmt_buffer);
+        adapter.init_with_config(channel, pin, config)?;
+        Ok(adapter)
+    }
+
+    /// Create an adapter that holds the buffer but isn't bound to an RMT
+    /// channel yet.
+    ///
+    /// This doesn't touch any hardware, so the adapter (and with it the
+    /// potentially large buffer) can be placed in a `static`. Call
+    /// [SmartLedsAdapter::init] before writing to it, until then writes fail
+    /// with [LedAdapterError::NotInitialized].
+    pub const fn new_uninit(rmt_buffer: [u32; BUFFER_SIZE]) -> Self {
+        const {
+            assert!(
+                BITS_PER_CHANNEL >= 1 && BITS_PER_CHANNEL <= 8,
+                "BITS_PER_CHANNEL must be between 1 and 8"
+            );
+            assert!(
+                BUFFER_SIZE % rmt_ram_one_led(BITS_PER_CHANNEL) == 1,
+                "BUFFER_SIZE must be sized with `buffer_size` or `buffer_size_with_bit_depth`"
+            )
+        };
+
+        Self {
+            channel: ChannelState::Uninitialized,
+            rmt_buffer,
+            pulses: BitPulses { zero: 0, one: 0 },
+            #[cfg(feature = "lut")]
+            lut: PulseLut::EMPTY,
+            reset_pulse: 0,
+            config: AdapterConfig::new(),
+            last_frame_end: None,
+            power: PowerScale::NONE,
+            #[cfg(feature = "stats")]
+            stats: FrameStats::new(),
+        }
+    }
+
+    /// Bind an adapter created with [SmartLedsAdapter::new_uninit] to the RMT
+    /// channel and pin.
+    pub fn init<C, O>(&mut self, channel: C, pin: O) -> Result<(), LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        self.init_with_config(channel, pin, AdapterConfig::default())
+    }
+
+    /// Bind an adapter created with [SmartLedsAdapter::new_uninit] to the RMT
+    /// channel and pin, using the given configuration.
+    pub fn init_with_config<C, O>(
+        &mut self,
+        channel: C,
+        pin: O,
+        config: AdapterConfig,
+    ) -> Result<(), LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        let channel = configure_channel(channel, pin, &config)?;
+        self.init_from_channel(channel, config)
+    }
+
+    /// Create a new adapter object that uses an RMT channel the caller has
+    /// already configured, e.g. with other memory or interrupt settings.
+    ///
+    /// The adapter only encodes and transmits the frames. The channel
+    /// settings of `config` aren't applied, but the pulse widths are still
+    /// calculated from its clock source and divider, and its memory size
+    /// decides whether a frame fits into the RAM of the channel for
+    /// [AdapterConfig::with_auto_refresh], so they have to match the channel.
+    /// [SmartLedsAdapter::reset] and the methods changing the configuration
+    /// re-apply the settings of the configuration to the channel.
+    ///
+    /// Fails with [LedAdapterError::TimingOutOfRange] if the pulse widths
+    /// can't be sent at the tick rate of the configuration.
+    pub fn from_channel(
+        channel: Channel<Blocking, TX>,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError> {
+        let mut adapter = Self::new_uninit(rmt_buffer);
+        adapter.init_from_channel(channel, config)?;
+        Ok(adapter)
+    }
+
+    fn init_from_channel(
+        &mut self,
+        channel: Channel<Blocking, TX>,
+        config: AdapterConfig,
+    ) -> Result<(), LedAdapterError> {
+        let tick_hz = config.tick_rate().as_hz();
+
+        self.pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+        #[cfg(feature = "lut")]
+        {
+            self.lut = PulseLut::new(self.pulses);
+        }
+        self.reset_pulse = reset_pulse_for_clock(tick_hz, config.timing(), config.inverted());
+        self.channel = ChannelState::Ready(channel);
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Time in microseconds it took to transmit the last frame.
+    ///
+    /// This only covers the RMT transmission, not the encoding of the colors.
+    #[cfg(feature = "stats")]
+    pub fn last_frame_us(&self) -> u64 {
+        self.stats.last_frame_us
+    }
+
+    /// Number of LEDs sent in the last frame.
+    #[cfg(feature = "stats")]
+    pub fn last_led_count(&self) -> usize {
+        self.stats.last_led_count
+    }
+
+    /// Timing and throughput statistics of the frames sent so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    /// Reset the statistics, e.g. at the start of a measurement.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = FrameStats::new();
+    }
+
+    /// Reinitialize the RMT channel, e.g. after a
+    /// [LedAdapterError::TransmissionError].
+    ///
+    /// This re-applies the channel configuration in place so the next write
+    /// starts from a clean state, without giving up the pin.
+    pub fn reset(&mut self) -> Result<(), LedAdapterError> {
+        let channel = self.channel.as_mut()?;
+        channel.apply_config(&led_config(&self.config))?;
+
+        Ok(())
+    }
+
+    /// Give up the adapter and return the configured RMT channel, e.g. to
+    /// reuse it for another peripheral. A repeating frame is stopped first.
+    ///
+    /// The pin can be kept by passing a reborrow of it to the constructor.
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+
+    /// Number of LEDs the buffer can hold.
+    pub const fn capacity_leds(&self) -> usize {
+        Self::CAPACITY
+    }
+
+    /// Length of the RMT buffer in pulse codes.
+    pub const fn buffer_len(&self) -> usize {
+        BUFFER_SIZE
+    }
+
+    /// Check whether a frame of `len` LEDs fits into the buffer, returning
+    /// the [LedAdapterError::BufferSizeExceeded] a write of it would fail
+    /// with otherwise.
+    ///
+    /// Writing from an iterator only notices a frame that is too long once
+    /// the buffer is full, so this allows rejecting it before anything is
+    /// encoded.
+    pub const fn check_len(&self, len: usize) -> Result<(), LedAdapterError> {
+        check_len(len, Self::CAPACITY)
+    }
+
+    /// Switch to the timing, color order and polarity of another chip, e.g. to
+    /// change between 400 kHz and 800 kHz, without giving up the RMT channel.
+    ///
+    /// Chips with a white channel, see [Chipset::channels], are rejected with
+    /// [LedAdapterError::UnsupportedChipset].
+    pub fn set_chipset(&mut self, chipset: Chipset) -> Result<(), LedAdapterError> {
+        if chipset.channels() != 3 {
+            return Err(LedAdapterError::UnsupportedChipset(chipset));
+        }
+        self.set_config(self.config.with_chipset(chipset))
+    }
+
+    /// Switch to other pulse widths without giving up the RMT channel, e.g.
+    /// while detecting the type of the connected strip.
+    pub fn set_timing(&mut self, timing: LedTiming) -> Result<(), LedAdapterError> {
+        self.set_config(self.config.with_timing(Timing::Custom(timing)))
+    }
+
+    /// Scale all colors written from now on by `brightness / 256`, see
+    /// [AdapterConfig::with_brightness].
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.config = self.config.with_brightness(brightness);
+    }
+
+    /// The brightness all colors are scaled with.
+    pub fn brightness(&self) -> u8 {
+        self.config.brightness()
+    }
+
+    /// Scale the red, green and blue channels of all colors written from now
+    /// on, see [AdapterConfig::with_correction].
+    pub fn set_correction(&mut self, correction: RGB8) {
+        self.config = self.config.with_correction(correction);
+    }
+
+    /// Tint all colors written from now on to the color of a light source,
+    /// see [AdapterConfig::with_color_temperature].
+    pub fn set_color_temperature(&mut self, color_temperature: ColorTemperature) {
+        self.config = self.config.with_color_temperature(color_temperature);
+    }
+
+    // Apply a new configuration to the channel and recompute the pulses.
+    fn set_config(&mut self, config: AdapterConfig) -> Result<(), LedAdapterError> {
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = self.channel.as_mut()?;
+        channel.apply_config(&led_config(&config))?;
+
+        self.pulses = pulses;
+        #[cfg(feature = "lut")]
+        {
+            self.lut = PulseLut::new(self.pulses);
+        }
+        self.reset_pulse = reset_pulse_for_clock(tick_hz, config.timing(), config.inverted());
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Write `count` LEDs, calling `f` with the index of each LED to obtain
+    /// its color.
+    ///
+    /// The colors are encoded as they are produced, so procedural effects
+    /// where the color is a function of the LED index don't need to
+    /// materialize the whole frame first.
+    pub fn write_with<F>(&mut self, count: usize, f: F) -> Result<(), LedAdapterError>
+    where
+        F: FnMut(usize) -> RGB8,
+    {
+        self.write((0..count).map(f))
+    }
+
+    /// Write colors given as `(r, g, b)` tuples.
+    pub fn write_tuples<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = (u8, u8, u8)>,
+    {
+        self.write(iterator.into_iter().map(|(r, g, b)| RGB8 { r, g, b }))
+    }
+
+    /// Write colors given as `[r, g, b]` arrays.
+    pub fn write_arrays<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = [u8; 3]>,
+    {
+        self.write(iterator.into_iter().map(|[r, g, b]| RGB8 { r, g, b }))
+    }
+
+    /// Write colors with 16 bits per channel, rounded to the nearest 8-bit
+    /// value.
+    ///
+    /// Use an adapter with a [TemporalDither] as its encoder, e.g. a
+    /// [SmartLedsAdapterCustom], instead of rounding to keep more of the
+    /// precision.
+    pub fn write_rgb16<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = RGB16>,
+    {
+        self.write(iterator.into_iter().map(round_rgb16))
+    }
+
+    /// Write a slice of colors.
+    ///
+    /// Unlike writing from an iterator, the length of the slice is validated
+    /// up front, so a slice that is too long leaves the buffer untouched. Every
+    /// color is encoded straight into its fixed position in the buffer, which
+    /// is faster than going through an iterator.
+    pub fn write_slice(&mut self, colors: &[RGB8]) -> Result<(), LedAdapterError> {
+        self.check_len(colors.len())?;
+
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+
+        // The slice can be iterated twice, so the power limit of this frame
+        // applies to it right away
+        self.power = PowerScale::of_colors(
+            &RgbBitsEncoder::<BITS_PER_CHANNEL>,
+            colors.iter().copied(),
+            &self.config,
+        );
+        let config = self.power.apply(&self.config);
+
+        let led_size = rmt_ram_one_led(BITS_PER_CHANNEL);
+        let (leds, rest) = self.rmt_buffer.split_at_mut(colors.len() * led_size);
+        for (color, out) in colors.iter().zip(leds.chunks_exact_mut(led_size)) {
+            #[cfg(feature = "lut")]
+            if BITS_PER_CHANNEL == 8 {
+                LutEncoder(&self.lut).encode(*color, &self.pulses, &config, out);
+                continue;
+            }
+
+            RgbBitsEncoder::<BITS_PER_CHANNEL>.encode(*color, &self.pulses, &config, out);
+        }
+        // The capacity leaves a slot for the end delimiter after the LEDs
+        rest[0] = self.reset_pulse;
+
+        #[cfg(feature = "stats")]
+        self.stats.record_encode(start, true);
+
+        self.transmit(colors.len())
+    }
+
+    /// Write the colors of the iterator like [SmartLedsWrite::write], but
+    /// wait at most `timeout` for the transmission to finish.
+    ///
+    /// If it takes longer, e.g. because interrupts kept the CPU from refilling
+    /// the RAM of the channel, [LedAdapterError::Timeout] is returned. The RMT
+    /// driver has no way to abort a transmission, so this still waits for it
+    /// to end, and the channel is ready for the next frame afterwards. Frames
+    /// aren't repeated with [AdapterConfig::with_auto_refresh] when written
+    /// this way.
+    pub fn write_timeout<T, I>(
+        &mut self,
+        iterator: T,
+        timeout: Duration,
+    ) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let led_count = self.encode(iterator)?;
+
+        self.transmit_with_timeout(led_count, Some(timeout))
+    }
+
+    /// Set all LEDs the buffer can hold to `color`.
+    ///
+    /// The color is only converted once and its pulses are copied to every
+    /// LED, which is much faster than encoding an iterator of identical
+    /// colors.
+    pub fn fill(&mut self, color: RGB8) -> Result<(), LedAdapterError> {
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+
+        let load = rgb_load(color, &self.config).saturating_mul(Self::CAPACITY as u32);
+        self.power = PowerScale::of(&self.config, Self::CAPACITY, load);
+        let config = self.power.apply(&self.config);
+
+        let led_size = rmt_ram_one_led(BITS_PER_CHANNEL);
+        let (leds, rest) = self.rmt_buffer.split_at_mut(Self::CAPACITY * led_size);
+        let (first, others) = leds.split_at_mut(led_size.min(leds.len()));
+        RgbBitsEncoder::<BITS_PER_CHANNEL>.encode(color, &self.pulses, &config, first);
+        for pulses in others.chunks_exact_mut(led_size) {
+            pulses.copy_from_slice(first);
+        }
+        // The capacity leaves a slot for the end delimiter after the LEDs
+        rest[0] = self.reset_pulse;
+
+        #[cfg(feature = "stats")]
+        self.stats.record_encode(start, true);
+
+        self.transmit(Self::CAPACITY)
+    }
+
+    /// Turn off all LEDs the buffer can hold.
+    pub fn clear(&mut self) -> Result<(), LedAdapterError> {
+        self.fill(RGB8::default())
+    }
+
+    /// Write the colors of the iterator and fill the remaining LEDs the
+    /// buffer can hold with `pad_color`.
+    ///
+    /// This always sends as many LEDs as the buffer was sized for, so e.g.
+    /// padding with black cleanly blanks the rest of the strip after a short
+    /// frame.
+    pub fn write_padded<T, I>(
+        &mut self,
+        iterator: T,
+        pad_color: RGB8,
+    ) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let mut colors = iterator.into_iter();
+        let led_count = self.encode(
+            colors
+                .by_ref()
+                .map(Into::into)
+                .chain(core::iter::repeat(pad_color))
+                .take(Self::CAPACITY),
+        )?;
+
+        if colors.next().is_some() {
+            return Err(LedAdapterError::BufferSizeExceeded {
+                needed: Self::CAPACITY + 1,
+                capacity: Self::CAPACITY,
+                encoded: Self::CAPACITY,
+            });
+        }
+
+        self.transmit(led_count)
+    }
+
+    /// Transmit pre-encoded RMT pulse codes as they are, skipping the color
+    /// conversion.
+    ///
+    /// This is useful for custom protocols or pulse data generated offline.
+    /// The pulses must end with an end delimiter, i.e. a pulse code with a
+    /// length of zero, and are sent with the timing of the channel, so they
+    /// have to be computed for the tick rate of the configured
+    /// [ClockSource]. The internal buffer is left untouched.
+    ///
+    /// Pulses without an end delimiter at the end are rejected with
+    /// [LedAdapterError::EndDelimiterMissing], as the RMT peripheral would
+    /// keep sending whatever follows them in its RAM.
+    pub fn write_raw(&mut self, pulses: &[u32]) -> Result<(), LedAdapterError> {
+        if !pulses.last().is_some_and(|&code| is_end_marker(code)) {
+            return Err(LedAdapterError::EndDelimiterMissing);
+        }
 
-        // Assume the RMT peripheral is set up to use the APB clock
-        let src_clock = Clocks::get().apb_clock.as_mhz();
+        wait_for_frame_gap(self.last_frame_end, self.config.frame_gap_ns());
+        let result = self.channel.transmit(pulses);
+        self.last_frame_end = Some(Instant::now());
 
-        Self {
-            channel: Some(channel),
-            rmt_buffer,
-            pulses: led_pulses_for_clock(src_clock),
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+
+    /// Encode the frame into the buffer and return the number of LEDs.
+    fn encode<I: Into<RGB8>>(
+        &mut self,
+        iterator: impl IntoIterator<Item = I>,
+    ) -> Result<usize, LedAdapterError> {
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let result = self.convert_frame(iterator);
+        #[cfg(feature = "stats")]
+        self.stats.record_encode(start, result.is_ok());
+
+        result
+    }
+
+    fn convert_frame<I: Into<RGB8>>(
+        &mut self,
+        iterator: impl IntoIterator<Item = I>,
+    ) -> Result<usize, LedAdapterError> {
+        #[cfg(feature = "lut")]
+        if BITS_PER_CHANNEL == 8 {
+            return encode_frame(
+                &mut LutEncoder(&self.lut),
+                &mut self.rmt_buffer,
+                iterator,
+                &self.pulses,
+                &self.config,
+                &mut self.power,
+                self.reset_pulse,
+            );
         }
+
+        encode_frame(
+            &mut RgbBitsEncoder::<BITS_PER_CHANNEL>,
+            &mut self.rmt_buffer,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )
+    }
+
+    /// Transmit the frame currently encoded in the buffer.
+    fn transmit(&mut self, led_count: usize) -> Result<(), LedAdapterError> {
+        self.transmit_with_timeout(led_count, None)
+    }
+
+    /// Transmit the first `led_count` LEDs encoded in the buffer, waiting at
+    /// most `timeout` for the transmission if given.
+    fn transmit_with_timeout(
+        &mut self,
+        led_count: usize,
+        timeout: Option<Duration>,
+    ) -> Result<(), LedAdapterError> {
+        let used = led_count * rmt_ram_one_led(BITS_PER_CHANNEL) + 1;
+
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let result = self.channel.send_frame(
+            &self.rmt_buffer[..used],
+            &self.config,
+            timeout,
+            &mut self.last_frame_end,
+        );
+
+        #[cfg(feature = "stats")]
+        self.stats.record_transmit(start, led_count, result.is_ok());
+
+        result
     }
 }
 
-impl<TX, const BUFFER_SIZE: usize> SmartLedsWrite for SmartLedsAdapter<TX, BUFFER_SIZE>
+impl<TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize> SmartLedsWrite
+    for SmartLedsAdapter<TX, BUFFER_SIZE, BITS_PER_CHANNEL>
 where
     TX: RawChannelAccess + TxChannelInternal + 'static,
 {
@@ -210,36 +2686,37 @@ where
     /// Convert all RGB8 items of the iterator to the RMT format and
     /// add them to internal buffer, then start a singular RMT operation
     /// based on that buffer.
+    ///
+    /// Only the LEDs provided by the iterator are sent. If the buffer holds
+    /// more LEDs, the remaining ones keep their current color; see
+    /// [SmartLedsAdapter::write_padded] to overwrite them as well.
     fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
     where
         T: IntoIterator<Item = I>,
         I: Into<Self::Color>,
     {
-        // We always start from the beginning of the buffer
-        let mut seq_iter = self.rmt_buffer.iter_mut();
+        let led_count = self.encode(iterator)?;
 
-        // Add all converted iterator items to the buffer.
-        // This will result in an `BufferSizeExceeded` error in case
-        // the iterator provides more elements than the buffer can take.
-        for item in iterator {
-            convert_rgb_to_pulses(item.into(), &mut seq_iter, self.pulses)?;
-        }
+        self.transmit(led_count)
+    }
+}
 
-        // Finally, add an end element.
-        *seq_iter.next().ok_or(LedAdapterError::BufferSizeExceeded)? = 0;
+/// Allows passing the adapter by reference to functions taking a writer by
+/// value.
+impl<TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize> SmartLedsWrite
+    for &mut SmartLedsAdapter<TX, BUFFER_SIZE, BITS_PER_CHANNEL>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    type Error = LedAdapterError;
+    type Color = RGB8;
 
-        // Perform the actual RMT operation. We use the u32 values here right away.
-        let channel = self.channel.take().unwrap();
-        match channel.transmit(&self.rmt_buffer)?.wait() {
-            Ok(chan) => {
-                self.channel = Some(chan);
-                Ok(())
-            }
-            Err((e, chan)) => {
-                self.channel = Some(chan);
-                Err(LedAdapterError::TransmissionError(e))
-            }
-        }
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        (**self).write(iterator)
     }
 }
 
@@ -248,20 +2725,98 @@ where
 /// Function to calculate the required RMT buffer size for a given number of LEDs when using
 /// the asynchronous API. This buffer size is calculated for the asynchronous API provided by the
 /// [SmartLedsAdapterAsync]. [buffer_size] should be used for the synchronous API.
+///
+/// The buffer is sized for the default of one block of RMT RAM, which holds a
+/// single LED per transaction (two on the ESP32 and ESP32-S2), and is large
+/// enough for any other [AdapterConfig::with_memsize] as well. Use
+/// [buffer_size_async_with_memsize] to save the delimiters of the
+/// transactions with more blocks.
 pub const fn buffer_size_async(num_leds: usize) -> usize {
-    // 1 byte end delimiter for each transfer.
-    num_leds * (RMT_RAM_ONE_LED + 1)
+    buffer_size_async_with_memsize(num_leds, 1)
+}
+
+/// Function to calculate the required RMT buffer size for a given number of LEDs when using
+/// the asynchronous API with `memsize` blocks of RMT RAM, see [AdapterConfig::with_memsize].
+///
+/// The buffer also works with more blocks, but holds fewer LEDs with less.
+pub const fn buffer_size_async_with_memsize(num_leds: usize, memsize: u8) -> usize {
+    // 1 end delimiter for each transaction
+    num_leds * RMT_RAM_ONE_LED + num_leds.div_ceil(leds_per_transaction(memsize))
+}
+
+// Number of LEDs the asynchronous adapter sends per transaction with `memsize`
+// blocks of RMT RAM, as many as fit into it along with the end delimiter.
+const fn leds_per_transaction(memsize: u8) -> usize {
+    let leds = (memsize as usize * RMT_BLOCK_SIZE).saturating_sub(1) / RMT_RAM_ONE_LED;
+    if leds == 0 {
+        1
+    } else {
+        leds
+    }
+}
+
+// Number of LEDs a buffer of `len` pulse codes holds for the asynchronous
+// adapter with `memsize` blocks of RMT RAM.
+const fn capacity_async(len: usize, memsize: u8) -> usize {
+    let per_transaction = leds_per_transaction(memsize);
+    let transaction = per_transaction * RMT_RAM_ONE_LED + 1;
+    let rest = len % transaction;
+    let partial = if rest > RMT_RAM_ONE_LED {
+        (rest - 1) / RMT_RAM_ONE_LED
+    } else {
+        0
+    };
+    len / transaction * per_transaction + partial
 }
 
 /// Adapter taking an RMT channel and a specific pin and providing RGB LED
 /// interaction functionality.
+///
+/// The asynchronous RMT driver can't refill the RAM of the channel during a
+/// transaction, so a frame is split into as few transactions as fit into it.
+///
+/// **With the default of one block of RMT RAM, every LED is sent in its own
+/// transaction (every two LEDs on the ESP32 and ESP32-S2).** The line is idle
+/// while the executor starts the next transaction, and if that takes longer
+/// than the reset time of the LEDs, they latch the partial frame and the
+/// write fails with [LedAdapterError::FrameSplit]. Use
+/// [AdapterConfig::with_memsize] to send more LEDs per transaction on long
+/// strips, and give the LED task a high priority.
+///
+/// Like [SmartLedsAdapter], the adapter is `Send` and has no lifetime, so it
+/// can be moved into a task spawned by an executor or kept in a `StaticCell`.
+///
+/// The adapter always sends 8 bits per color channel. LED types with another
+/// bit depth need the blocking [SmartLedsAdapter::new_with_bit_depth].
 pub struct SmartLedsAdapterAsync<Tx, const BUFFER_SIZE: usize>
 where
     Tx: RawChannelAccess + TxChannelInternal + 'static,
 {
     channel: Channel<Async, Tx>,
     rmt_buffer: [u32; BUFFER_SIZE],
-    pulses: (u32, u32),
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+    // End of the transmission in flight, only left set if the write awaiting
+    // it was cancelled or the frame was started by `start_write`
+    busy_until: Option<Instant>,
+    // Frame sent from interrupt handlers by `start_write`
+    interrupt_frame: Option<InterruptFrame>,
+    #[cfg(feature = "stats")]
+    stats: FrameStats,
+}
+
+/// Progress of a frame sent by [SmartLedsAdapterAsync::start_write].
+#[derive(Clone, Copy)]
+struct InterruptFrame {
+    // Start of the next transaction in the buffer
+    next: usize,
+    // Number of pulse codes of the frame
+    used: usize,
+    on_sent: fn(),
 }
 
 impl<'d, Tx, const BUFFER_SIZE: usize> SmartLedsAdapterAsync<Tx, BUFFER_SIZE>
@@ -269,6 +2824,11 @@ where
     Tx: RawChannelAccess + TxChannelInternal + 'static,
 {
     /// Create a new adapter object that drives the pin using the RMT channel.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterAsync::try_new].
     pub fn new<C, O>(
         channel: C,
         pin: O,
@@ -278,45 +2838,653 @@ where
         O: PeripheralOutput<'d>,
         C: TxChannelCreator<'d, Async, Raw = Tx>,
     {
-        let channel = channel.configure_tx(pin, led_config()).unwrap();
+        Self::new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// returning an error instead of panicking if the channel can't be
+    /// configured.
+    pub fn try_new<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+    ) -> Result<SmartLedsAdapterAsync<Tx, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Async, Raw = Tx>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterAsync::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> SmartLedsAdapterAsync<Tx, BUFFER_SIZE>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Async, Raw = Tx>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffer, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, returning an error instead of panicking
+    /// if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<SmartLedsAdapterAsync<Tx, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Async, Raw = Tx>,
+    {
+        let channel = configure_channel(channel, pin, &config)?;
 
-        // Assume the RMT peripheral is set up to use the APB clock
-        let src_clock = Clocks::get().apb_clock.as_mhz();
+        Self::from_channel(channel, rmt_buffer, config)
+    }
 
-        Self {
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, with the RMT interrupt set to `priority`
+    /// and handled on the calling core.
+    ///
+    /// See [SmartLedsAdapterAsync::set_interrupt_priority].
+    pub fn try_new_with_interrupt_priority<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+        priority: Priority,
+    ) -> Result<SmartLedsAdapterAsync<Tx, BUFFER_SIZE>, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Async, Raw = Tx>,
+    {
+        let mut adapter = Self::try_new_with_config(channel, pin, rmt_buffer, config)?;
+        adapter.set_interrupt_priority(priority)?;
+        Ok(adapter)
+    }
+
+    /// Create a new adapter object that uses an RMT channel the caller has
+    /// already configured.
+    ///
+    /// See [SmartLedsAdapter::from_channel].
+    pub fn from_channel(
+        channel: Channel<Async, Tx>,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError> {
+        let tick_hz = config.tick_rate().as_hz();
+
+        Ok(Self {
             channel,
             rmt_buffer,
-            pulses: led_pulses_for_clock(src_clock),
+            pulses: led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            last_frame_end: None,
+            power: PowerScale::NONE,
+            busy_until: None,
+            interrupt_frame: None,
+            #[cfg(feature = "stats")]
+            stats: FrameStats::new(),
+        })
+    }
+
+    /// Time in microseconds it took to transmit the last frame.
+    ///
+    /// This only covers the RMT transmissions, not the encoding of the colors.
+    #[cfg(feature = "stats")]
+    pub fn last_frame_us(&self) -> u64 {
+        self.stats.last_frame_us
+    }
+
+    /// Number of LEDs sent in the last frame.
+    #[cfg(feature = "stats")]
+    pub fn last_led_count(&self) -> usize {
+        self.stats.last_led_count
+    }
+
+    /// Timing and throughput statistics of the frames sent so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    /// Reset the statistics, e.g. at the start of a measurement.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = FrameStats::new();
+    }
+
+    /// Reinitialize the RMT channel, e.g. after a
+    /// [LedAdapterError::TransmissionError].
+    ///
+    /// See [SmartLedsAdapter::reset].
+    pub fn reset(&mut self) -> Result<(), LedAdapterError> {
+        self.channel.apply_config(&led_config(&self.config))?;
+
+        Ok(())
+    }
+
+    /// Set the priority of the RMT interrupt that wakes pending writes, and
+    /// handle it on the core calling this.
+    ///
+    /// The interrupt is shared by all channels of the RMT peripheral, so this
+    /// affects other async RMT drivers as well. By default it is enabled when
+    /// the peripheral is converted with `Rmt::into_async`, on the core doing
+    /// that. Lowering the priority below that of
+    /// e.g. audio or radio tasks lets them preempt the LED refresh.
+    pub fn set_interrupt_priority(&mut self, priority: Priority) -> Result<(), LedAdapterError> {
+        // Only one core should handle the interrupt
+        #[cfg(any(feature = "esp32", feature = "esp32s3"))]
+        for core in [Cpu::ProCpu, Cpu::AppCpu] {
+            if core != Cpu::current() {
+                interrupt::disable(core, Interrupt::RMT);
+            }
+        }
+
+        interrupt::enable(Interrupt::RMT, priority)?;
+
+        Ok(())
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release]. If a write was cancelled, this waits
+    /// for the hardware to finish its transmission first, yielding to the
+    /// executor in the meantime.
+    pub async fn release(self) -> Channel<Async, Tx> {
+        if let Some(busy_until) = self.busy_until {
+            yield_until(busy_until).await;
         }
+        self.channel
     }
 
-    fn prepare_rmt_buffer<I: Into<RGB8>>(
-        &mut self,
-        iterator: impl IntoIterator<Item = I>,
-    ) -> Result<(), LedAdapterError> {
-        // We always start from the beginning of the buffer
-        let mut seq_iter = self.rmt_buffer.iter_mut();
+    /// Number of LEDs the buffer can hold, which depends on the number of
+    /// LEDs per transaction, see [buffer_size_async_with_memsize].
+    pub const fn capacity_leds(&self) -> usize {
+        capacity_async(BUFFER_SIZE, self.config.memsize())
+    }
 
-        // Add all converted iterator items to the buffer.
-        // This will result in an `BufferSizeExceeded` error in case
-        // the iterator provides more elements than the buffer can take.
-        for item in iterator {
-            Self::convert_rgb_to_pulse(item.into(), &mut seq_iter, self.pulses)?;
+    /// Length of the RMT buffer in pulse codes.
+    pub const fn buffer_len(&self) -> usize {
+        BUFFER_SIZE
+    }
+
+    /// Check whether a frame of `len` LEDs fits into the buffer, returning
+    /// the [LedAdapterError::BufferSizeExceeded] a write of it would fail
+    /// with otherwise.
+    ///
+    /// See [SmartLedsAdapter::check_len].
+    pub const fn check_len(&self, len: usize) -> Result<(), LedAdapterError> {
+        check_len(len, self.capacity_leds())
+    }
+
+    /// Switch to the timing, color order and polarity of another chip without
+    /// giving up the RMT channel.
+    ///
+    /// See [SmartLedsAdapter::set_chipset].
+    pub fn set_chipset(&mut self, chipset: Chipset) -> Result<(), LedAdapterError> {
+        if chipset.channels() != 3 {
+            return Err(LedAdapterError::UnsupportedChipset(chipset));
         }
+        self.set_config(self.config.with_chipset(chipset))
+    }
+
+    /// Switch to other pulse widths without giving up the RMT channel.
+    ///
+    /// See [SmartLedsAdapter::set_timing].
+    pub fn set_timing(&mut self, timing: LedTiming) -> Result<(), LedAdapterError> {
+        self.set_config(self.config.with_timing(Timing::Custom(timing)))
+    }
+
+    /// Scale all colors written from now on by `brightness / 256`, see
+    /// [AdapterConfig::with_brightness].
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.config = self.config.with_brightness(brightness);
+    }
+
+    /// The brightness all colors are scaled with.
+    pub fn brightness(&self) -> u8 {
+        self.config.brightness()
+    }
+
+    /// Scale the red, green and blue channels of all colors written from now
+    /// on, see [AdapterConfig::with_correction].
+    pub fn set_correction(&mut self, correction: RGB8) {
+        self.config = self.config.with_correction(correction);
+    }
+
+    /// Tint all colors written from now on to the color of a light source,
+    /// see [AdapterConfig::with_color_temperature].
+    pub fn set_color_temperature(&mut self, color_temperature: ColorTemperature) {
+        self.config = self.config.with_color_temperature(color_temperature);
+    }
+
+    // Apply a new configuration to the channel and recompute the pulses.
+    fn set_config(&mut self, config: AdapterConfig) -> Result<(), LedAdapterError> {
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        self.channel.apply_config(&led_config(&config))?;
+
+        self.pulses = pulses;
+        self.reset_pulse = reset_pulse_for_clock(tick_hz, config.timing(), config.inverted());
+        self.config = config;
+
         Ok(())
     }
 
-    /// Converts a RGB value to the correspodnign pulse value.
-    fn convert_rgb_to_pulse(
-        value: RGB8,
-        mut_iter: &mut IterMut<u32>,
-        pulses: (u32, u32),
-    ) -> Result<(), LedAdapterError> {
-        convert_rgb_to_pulses(value, mut_iter, pulses)?;
-        *mut_iter.next().ok_or(LedAdapterError::BufferSizeExceeded)? = 0;
+    /// Write `count` LEDs, calling `f` with the index of each LED to obtain
+    /// its color.
+    ///
+    /// See [SmartLedsAdapter::write_with].
+    pub async fn write_with<F>(&mut self, count: usize, f: F) -> Result<(), LedAdapterError>
+    where
+        F: FnMut(usize) -> RGB8,
+    {
+        self.write((0..count).map(f)).await
+    }
+
+    /// Write colors given as `(r, g, b)` tuples.
+    pub async fn write_tuples<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = (u8, u8, u8)>,
+    {
+        self.write(iterator.into_iter().map(|(r, g, b)| RGB8 { r, g, b }))
+            .await
+    }
+
+    /// Write colors given as `[r, g, b]` arrays.
+    pub async fn write_arrays<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = [u8; 3]>,
+    {
+        self.write(iterator.into_iter().map(|[r, g, b]| RGB8 { r, g, b }))
+            .await
+    }
+
+    /// Write colors with 16 bits per channel, rounded to the nearest 8-bit
+    /// value.
+    ///
+    /// Use an adapter with a [TemporalDither] as its encoder, e.g. a
+    /// [SmartLedsAdapterCustom], instead of rounding to keep more of the
+    /// precision.
+    pub async fn write_rgb16<T>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = RGB16>,
+    {
+        self.write(iterator.into_iter().map(round_rgb16)).await
+    }
+
+    /// Write a slice of colors.
+    ///
+    /// See [SmartLedsAdapter::write_slice].
+    pub async fn write_slice(&mut self, colors: &[RGB8]) -> Result<(), LedAdapterError> {
+        self.check_len(colors.len())?;
+
+        self.write(colors.iter().copied()).await
+    }
+
+    /// Set all LEDs the buffer can hold to `color`.
+    pub async fn fill(&mut self, color: RGB8) -> Result<(), LedAdapterError> {
+        self.write(core::iter::repeat_n(color, self.capacity_leds()))
+            .await
+    }
+
+    /// Turn off all LEDs the buffer can hold.
+    pub async fn clear(&mut self) -> Result<(), LedAdapterError> {
+        self.fill(RGB8::default()).await
+    }
+
+    /// Write every frame of the stream as soon as it arrives, e.g. frames
+    /// received over the network by another task, until the stream ends.
+    ///
+    /// Frames are sent back to back, with at least the frame gap between
+    /// them for the LEDs to latch each one. Between frames, other tasks get a
+    /// chance to run even if the stream is always ready. The first error
+    /// stops the stream, pass it by `&mut` to continue with the remaining
+    /// frames.
+    #[cfg(feature = "futures-core")]
+    pub async fn write_stream<S, T, I>(&mut self, stream: S) -> Result<(), LedAdapterError>
+    where
+        S: futures_core::Stream<Item = T>,
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let mut stream = core::pin::pin!(stream);
+        while let Some(frame) = core::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            self.write(frame).await?;
+
+            let mut yielded = false;
+            core::future::poll_fn(|cx| {
+                if yielded {
+                    core::task::Poll::Ready(())
+                } else {
+                    yielded = true;
+                    cx.waker().wake_by_ref();
+                    core::task::Poll::Pending
+                }
+            })
+            .await;
+        }
+
+        Ok(())
+    }
+
+    /// Write the colors of the iterator from non-async code, busy-waiting for
+    /// the transmission instead of yielding to the executor.
+    ///
+    /// This is meant for places without an executor, e.g. a panic handler,
+    /// early boot or shutdown. The transmission is polled in a loop instead of
+    /// waiting for the RMT interrupt to wake the task.
+    pub fn write_blocking<T, I>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let led_count = self.encode(iterator)?;
+        block_on(self.transmit(led_count))
+    }
+
+    /// Write the colors of the iterator and fill the remaining LEDs the
+    /// buffer can hold with `pad_color`.
+    ///
+    /// See [SmartLedsAdapter::write_padded].
+    pub async fn write_padded<T, I>(
+        &mut self,
+        iterator: T,
+        pad_color: RGB8,
+    ) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let capacity = self.capacity_leds();
+        let mut colors = iterator.into_iter();
+        let led_count = self.encode(
+            colors
+                .by_ref()
+                .map(Into::into)
+                .chain(core::iter::repeat(pad_color))
+                .take(capacity),
+        )?;
+
+        if colors.next().is_some() {
+            return Err(LedAdapterError::BufferSizeExceeded {
+                needed: capacity + 1,
+                capacity,
+                encoded: capacity,
+            });
+        }
+
+        self.transmit(led_count).await
+    }
+
+    /// Start sending the colors of the iterator and return right away, to
+    /// refresh the LEDs from interrupt handlers without an executor.
+    ///
+    /// The frame is sent in as many transactions as
+    /// [SmartLedsAdapterAsync::leds_per_transaction] requires. `on_sent` is
+    /// called from the RMT interrupt whenever one of them is done, and has to
+    /// call [SmartLedsAdapterAsync::continue_write] on the adapter, e.g.
+    /// through a `critical_section::Mutex`, which starts the next transaction
+    /// and tells when the whole frame has been sent, so the next frame can be
+    /// started right from the interrupt:
+    ///
+    /// ```rust,ignore
+    /// fn on_sent() {
+    ///     critical_section::with(|cs| {
+    ///         let mut led = LED.borrow_ref_mut(cs);
+    ///         if let Ok(true) = led.continue_write() {
+    ///             led.start_write(next_frame(), on_sent).ok();
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// The RMT interrupt is only handled once the peripheral was converted
+    /// with `Rmt::into_async`. If the frame gap hasn't passed yet, this
+    /// busy-waits for it, which with the default configuration only happens
+    /// for LEDs with a reset time longer than the end delimiter can hold.
+    /// Fails with [LedAdapterError::Busy] while the previous frame is still
+    /// being sent. Transmission errors of the started transactions can't be
+    /// reported, and an async write started in the meantime drops the
+    /// remaining transactions of the frame.
+    pub fn start_write<T, I>(&mut self, iterator: T, on_sent: fn()) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        if self.interrupt_frame.is_some() || self.busy_until.is_some_and(|t| Instant::now() < t) {
+            return Err(LedAdapterError::Busy);
+        }
+        if let Some(busy_until) = self.busy_until.take() {
+            self.last_frame_end = Some(busy_until);
+        }
+
+        let led_count = self.encode(iterator)?;
+        let per_transaction = self.leds_per_transaction();
+        let used = led_count * RMT_RAM_ONE_LED + led_count.div_ceil(per_transaction);
+        if used == 0 {
+            return Ok(());
+        }
+
+        wait_for_frame_gap(self.last_frame_end, self.config.frame_gap_ns());
+        self.interrupt_frame = Some(InterruptFrame {
+            next: 0,
+            used,
+            on_sent,
+        });
+        self.start_transaction()
+    }
+
+    /// Start the next transaction of a frame sent with
+    /// [SmartLedsAdapterAsync::start_write], to be called from its `on_sent`
+    /// function.
+    ///
+    /// Returns `true` once the whole frame has been sent, or if no frame was
+    /// started. `on_sent` is only called once the RMT interrupt reports the
+    /// transaction in flight as done, so every call is taken as its
+    /// completion and this must not be called from anywhere else.
+    pub fn continue_write(&mut self) -> Result<bool, LedAdapterError> {
+        let Some(frame) = self.interrupt_frame else {
+            return Ok(true);
+        };
+
+        // The transaction is done, `busy_until` only bounds it for async
+        // writes started in the meantime and would be too late here.
+        self.busy_until = None;
+        if frame.next < frame.used {
+            self.start_transaction()?;
+            return Ok(false);
+        }
+
+        self.interrupt_frame = None;
+        self.last_frame_end = Some(Instant::now());
+        Ok(true)
+    }
+
+    /// Start the next transaction of the frame sent from interrupt handlers.
+    fn start_transaction(&mut self) -> Result<(), LedAdapterError> {
+        let per_transaction = self.leds_per_transaction();
+        let Some(frame) = self.interrupt_frame.as_mut() else {
+            return Ok(());
+        };
+        let start = frame.next;
+        let end = frame
+            .used
+            .min(start + per_transaction * RMT_RAM_ONE_LED + 1);
+        frame.next = end;
+        let on_sent = frame.on_sent;
+
+        self.busy_until =
+            Some(Instant::now() + self.transmission_time(&self.rmt_buffer[start..end]));
+        let waker = interrupt_waker(on_sent);
+        let mut cx = Context::from_waker(&waker);
+        // The future can't be kept, its type can't be named and it borrows the
+        // adapter. In esp-hal 1.0.0-rc.0 (`rmt.rs`), `TxChannelAsync::transmit`
+        // checks the length, listens for the end and error events of the
+        // channel and starts sending with `start_send` before it first awaits
+        // `RmtTxFuture`, whose `poll` registers the waker in the `WAKER` of the
+        // channel. `RmtTxFuture` has no `Drop` implementation, so dropping it
+        // after the first poll neither stops the transaction nor unregisters
+        // the waker, and `async_interrupt_handler` wakes it once the channel
+        // reports the end or an error. Only the error status is lost, which
+        // the future would check after that.
+        let transmit = pin!(self.channel.transmit(&self.rmt_buffer[start..end]));
+        if let Poll::Ready(Err(error)) = transmit.poll(&mut cx) {
+            self.busy_until = None;
+            self.interrupt_frame = None;
+            warn!("LED transmission failed: {:?}", error);
+            return Err(LedAdapterError::TransmissionError(error));
+        }
 
         Ok(())
     }
+
+    /// Number of LEDs sent per RMT transaction, as many as fit into the RAM of
+    /// the channel along with the end delimiter.
+    ///
+    /// See [AdapterConfig::with_memsize] to increase it.
+    pub const fn leds_per_transaction(&self) -> usize {
+        leds_per_transaction(self.config.memsize())
+    }
+
+    /// Upper bound of the time it takes to send the pulse codes of a
+    /// transaction. One more microsecond covers the resolution of [Instant].
+    fn transmission_time(&self, pulses: &[u32]) -> Duration {
+        Duration::from_micros(self.transmission_ns(pulses).div_ceil(1000) + 1)
+    }
+
+    /// Time it takes to send the pulse codes of a transaction, from the ticks
+    /// the channel actually sends.
+    fn transmission_ns(&self, pulses: &[u32]) -> u64 {
+        let ticks: u64 = pulses.iter().map(|&code| pulse_code_ticks(code)).sum();
+        let tick_hz = self.config.tick_rate().as_hz().max(1) as u64;
+        ticks * 1_000_000_000 / tick_hz
+    }
+
+    /// Transmit the first `led_count` LEDs encoded in the buffer.
+    ///
+    /// If the future is dropped, the RMT driver can't abort the transmission
+    /// in flight and the hardware finishes sending it. The next write awaits
+    /// that before touching the channel, followed by the frame gap, so the
+    /// LEDs latch the partial frame and the next one arrives intact.
+    async fn transmit(&mut self, led_count: usize) -> Result<(), LedAdapterError> {
+        self.interrupt_frame = None;
+        if let Some(busy_until) = self.busy_until {
+            yield_until(busy_until).await;
+            self.busy_until = None;
+            self.last_frame_end = Some(busy_until);
+        }
+        frame_gap(self.last_frame_end, self.config.frame_gap_ns()).await;
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let mut result = Ok(());
+        let per_transaction = self.leds_per_transaction();
+        let used = led_count * RMT_RAM_ONE_LED + led_count.div_ceil(per_transaction);
+        let reset_ns = self.config.timing().led_timing().reset as u64;
+        // Start and duration of the last transaction
+        let mut last: Option<(Instant, u64)> = None;
+        let chunks = self.rmt_buffer[..used].chunks(per_transaction * RMT_RAM_ONE_LED + 1);
+        for (index, chunk) in chunks.enumerate() {
+            // The line is idle from the end of the last transaction until this
+            // one starts. One microsecond is left for the resolution of
+            // `Instant`, so only gaps that certainly latched the LEDs count.
+            if let Some((start, sent_ns)) = last {
+                let idle_ns = (start.elapsed().as_micros() * 1000).saturating_sub(sent_ns + 1000);
+                if reset_ns > 0 && idle_ns > reset_ns {
+                    let sent = index * per_transaction;
+                    warn!("LEDs latched the frame after {} LEDs", sent);
+                    result = Err(LedAdapterError::FrameSplit { sent });
+                    break;
+                }
+            }
+
+            last = Some((Instant::now(), self.transmission_ns(chunk)));
+            self.busy_until = Some(Instant::now() + self.transmission_time(chunk));
+            let sent = self.channel.transmit(chunk).await;
+            self.busy_until = None;
+            if let Err(error) = sent {
+                let sent = index * per_transaction;
+                warn!("LED transmission failed after {} LEDs: {:?}", sent, error);
+                result = Err(LedAdapterError::PartialTransmission { error, sent });
+                break;
+            }
+        }
+        self.last_frame_end = Some(Instant::now());
+
+        #[cfg(feature = "stats")]
+        self.stats.record_transmit(start, led_count, result.is_ok());
+
+        result
+    }
+
+    /// Encode the frame into the buffer and return the number of LEDs.
+    fn encode<I: Into<RGB8>>(
+        &mut self,
+        iterator: impl IntoIterator<Item = I>,
+    ) -> Result<usize, LedAdapterError> {
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let result = self.prepare_rmt_buffer(iterator);
+        #[cfg(feature = "stats")]
+        self.stats.record_encode(start, result.is_ok());
+
+        result
+    }
+
+    /// Fills the RMT buffer and returns the number of LEDs encoded.
+    fn prepare_rmt_buffer<I: Into<RGB8>>(
+        &mut self,
+        iterator: impl IntoIterator<Item = I>,
+    ) -> Result<usize, LedAdapterError> {
+        let per_transaction = self.leds_per_transaction();
+        let capacity = self.capacity_leds();
+
+        // Every transaction worth of LEDs is followed by its end delimiter.
+        // The capacity keeps the slot for the delimiter of the last one.
+        let led_count = encode_leds(
+            &mut Rgb8Encoder,
+            &mut self.rmt_buffer,
+            iterator,
+            capacity,
+            |led| led * RMT_RAM_ONE_LED + led / per_transaction,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+        )?;
+
+        // Only the delimiter of the last transaction holds the line low for the
+        // reset time, the others have to end the transaction right away.
+        let transactions = led_count.div_ceil(per_transaction);
+        for transaction in 0..transactions {
+            let end = led_count.min((transaction + 1) * per_transaction);
+            let delimiter = if transaction + 1 == transactions {
+                self.reset_pulse
+            } else {
+                0
+            };
+            *self
+                .rmt_buffer
+                .get_mut(end * RMT_RAM_ONE_LED + transaction)
+                .ok_or(LedAdapterError::DelimiterSlotMissing)? = delimiter;
+        }
+
+        Ok(led_count)
+    }
 }
 
 impl<Tx, const BUFFER_SIZE: usize> SmartLedsWriteAsync for SmartLedsAdapterAsync<Tx, BUFFER_SIZE>
@@ -329,18 +3497,51 @@ where
     /// Convert all RGB8 items of the iterator to the RMT format and
     /// add them to internal buffer, then start perform all asynchronous operations based on
     /// that buffer.
+    ///
+    /// Only the LEDs provided by the iterator are sent, see
+    /// [SmartLedsAdapterAsync::write_padded] to overwrite the remaining ones
+    /// as well.
+    ///
+    /// The async RMT driver doesn't refill the RAM of the channel, so the frame
+    /// is only sent in a single transaction if it fits into the RAM, see
+    /// [AdapterConfig::with_memsize]. Otherwise it is split into transactions
+    /// of [SmartLedsAdapterAsync::leds_per_transaction] LEDs each, with the
+    /// line idle in between while the executor starts the next one. If that
+    /// gap exceeds the reset time of the LEDs, they latch the partial frame
+    /// and [LedAdapterError::FrameSplit] reports how many LEDs were sent.
+    ///
+    /// If one of the transactions fails, the remaining LEDs are not sent and
+    /// a [LedAdapterError::PartialTransmission] reports how many LEDs were
+    /// updated, so the caller can decide whether to send the frame again.
+    ///
+    /// The future is cancel-safe: if it is dropped, e.g. by `select` or a
+    /// timeout, the adapter can be written to again right away. The LEDs
+    /// keep the part of the frame that was already sent.
     async fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
     where
         T: IntoIterator<Item = I>,
         I: Into<Self::Color>,
     {
-        self.prepare_rmt_buffer(iterator)?;
-        for chunk in self.rmt_buffer.chunks(RMT_RAM_ONE_LED + 1) {
-            self.channel
-                .transmit(chunk)
-                .await
-                .map_err(LedAdapterError::TransmissionError)?;
-        }
-        Ok(())
+        let led_count = self.encode(iterator)?;
+        self.transmit(led_count).await
+    }
+}
+
+/// Allows passing the adapter by reference to functions taking a writer by
+/// value.
+impl<Tx, const BUFFER_SIZE: usize> SmartLedsWriteAsync
+    for &mut SmartLedsAdapterAsync<Tx, BUFFER_SIZE>
+where
+    Tx: RawChannelAccess + TxChannelInternal + 'static,
+{
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    async fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        (**self).write(iterator).await
     }
 }
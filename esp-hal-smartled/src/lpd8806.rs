@@ -0,0 +1,62 @@
+//! Support for clocked LPD8806 LEDs with 7 bits per color channel, driven by
+//! an SPI peripheral.
+
+use esp_hal::{spi::master::Spi, Blocking};
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+use crate::LedAdapterError;
+
+/// Adapter taking a blocking SPI driver and providing LPD8806 LED interaction
+/// functionality using the `smart-leds` crate
+///
+/// Connect the LED clock line to SCK and the data line to MOSI of the SPI
+/// driver. The lowest bit of every color channel is dropped, as the LEDs only
+/// support 7 bits.
+pub struct Lpd8806Adapter<'d> {
+    spi: Spi<'d, Blocking>,
+}
+
+impl<'d> Lpd8806Adapter<'d> {
+    /// Create a new adapter object that drives the LEDs using the SPI driver.
+    pub fn new(spi: Spi<'d, Blocking>) -> Self {
+        Self { spi }
+    }
+
+    /// Return the underlying SPI driver.
+    pub fn into_inner(self) -> Spi<'d, Blocking> {
+        self.spi
+    }
+}
+
+impl SmartLedsWrite for Lpd8806Adapter<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    /// Send one frame per item of the iterator followed by the latch bytes
+    /// over SPI.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let mut led_count: usize = 0;
+        for item in iterator {
+            let color = item.into();
+            // The high bit marks a data byte, the channels are sent as GRB.
+            self.spi.write(&[
+                0x80 | (color.g >> 1),
+                0x80 | (color.r >> 1),
+                0x80 | (color.b >> 1),
+            ])?;
+            led_count += 1;
+        }
+
+        // A zero byte per 32 LEDs resets the LEDs to latch the data and to be
+        // ready for the next frame.
+        for _ in 0..led_count.div_ceil(32).max(1) {
+            self.spi.write(&[0x00])?;
+        }
+
+        Ok(())
+    }
+}
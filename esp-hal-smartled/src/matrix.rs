@@ -0,0 +1,251 @@
+//! Mapping of the pixels of an LED matrix to the position of the LEDs on the
+//! strip.
+
+/// Mapping of the `(x, y)` coordinates of a matrix to the index of the LED on
+/// the strip, with `(0, 0)` in the top left corner.
+pub trait PixelMapping {
+    /// Width of the matrix in pixels.
+    fn width(&self) -> u32;
+
+    /// Height of the matrix in pixels.
+    fn height(&self) -> u32;
+
+    /// Index of the LED at `(x, y)` on the strip, `None` for coordinates
+    /// outside of the matrix or without an LED.
+    fn index(&self, x: u32, y: u32) -> Option<usize>;
+}
+
+/// Matrix whose rows are all wired from left to right, starting in the top
+/// left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Progressive {
+    width: u32,
+    height: u32,
+}
+
+impl Progressive {
+    /// Create a mapping for a matrix of `width` by `height` LEDs.
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+}
+
+impl PixelMapping for Progressive {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some((y * self.width + x) as usize)
+    }
+}
+
+/// Order the LEDs of the rows of a matrix, or the panels of a tiled matrix,
+/// are wired in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Wiring {
+    /// All rows run in the same direction.
+    #[default]
+    Progressive,
+    /// Every other row runs in the opposite direction, i.e. the wire zigzags
+    /// back and forth.
+    Serpentine,
+}
+
+/// Corner of a panel its first LED is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Corner {
+    /// The top left corner, with the first row running to the right.
+    #[default]
+    TopLeft,
+    /// The top right corner, with the first row running to the left.
+    TopRight,
+    /// The bottom left corner, with the first row running to the right.
+    BottomLeft,
+    /// The bottom right corner, with the first row running to the left.
+    BottomRight,
+}
+
+/// Clockwise rotation of the image on the matrix, e.g. to account for the
+/// way it is mounted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rotation {
+    /// Not rotated.
+    #[default]
+    Deg0,
+    /// Rotated by 90°, which swaps width and height.
+    Deg90,
+    /// Rotated by 180°.
+    Deg180,
+    /// Rotated by 270°, which swaps width and height.
+    Deg270,
+}
+
+/// Mapping of a matrix made of one or more identical panels of LEDs.
+///
+/// The LEDs of each panel are wired row by row from its [Corner], either
+/// progressive or serpentine. Tiled panels are chained row by row from the
+/// top left one, and all have their first LED in the same corner.
+///
+/// ```rust,ignore
+/// // Two 8x8 serpentine panels next to each other, mounted upside down
+/// let map = MatrixMap::new(8, 8)
+///     .with_wiring(Wiring::Serpentine)
+///     .with_tiles(2, 1)
+///     .with_rotation(Rotation::Deg180);
+/// let index = map.index(3, 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MatrixMap {
+    panel_width: u32,
+    panel_height: u32,
+    wiring: Wiring,
+    origin: Corner,
+    rotation: Rotation,
+    tiles_x: u32,
+    tiles_y: u32,
+    tile_wiring: Wiring,
+}
+
+impl MatrixMap {
+    /// Create a mapping for a single progressive panel of `width` by `height`
+    /// LEDs, starting in the top left corner.
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self {
+            panel_width: width,
+            panel_height: height,
+            wiring: Wiring::Progressive,
+            origin: Corner::TopLeft,
+            rotation: Rotation::Deg0,
+            tiles_x: 1,
+            tiles_y: 1,
+            tile_wiring: Wiring::Progressive,
+        }
+    }
+
+    /// Set the order the rows of every panel are wired in.
+    ///
+    /// Defaults to [Wiring::Progressive].
+    pub const fn with_wiring(mut self, wiring: Wiring) -> Self {
+        self.wiring = wiring;
+        self
+    }
+
+    /// Set the corner of every panel its first LED is in.
+    ///
+    /// Defaults to [Corner::TopLeft].
+    pub const fn with_origin(mut self, origin: Corner) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Set the clockwise rotation of the image on the matrix.
+    ///
+    /// Defaults to [Rotation::Deg0].
+    pub const fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the number of panels next to and above each other.
+    ///
+    /// Defaults to a single panel.
+    pub const fn with_tiles(mut self, columns: u32, rows: u32) -> Self {
+        self.tiles_x = columns;
+        self.tiles_y = rows;
+        self
+    }
+
+    /// Set the order the rows of panels are chained in.
+    ///
+    /// Defaults to [Wiring::Progressive].
+    pub const fn with_tile_wiring(mut self, tile_wiring: Wiring) -> Self {
+        self.tile_wiring = tile_wiring;
+        self
+    }
+
+    /// Total number of LEDs of the matrix.
+    pub const fn len(&self) -> usize {
+        (self.panel_width * self.panel_height * self.tiles_x * self.tiles_y) as usize
+    }
+
+    /// Whether the matrix has no LEDs.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Size of the matrix before the rotation.
+    const fn unrotated_size(&self) -> (u32, u32) {
+        (
+            self.panel_width * self.tiles_x,
+            self.panel_height * self.tiles_y,
+        )
+    }
+}
+
+impl PixelMapping for MatrixMap {
+    fn width(&self) -> u32 {
+        let (width, height) = self.unrotated_size();
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => width,
+            Rotation::Deg90 | Rotation::Deg270 => height,
+        }
+    }
+
+    fn height(&self) -> u32 {
+        let (width, height) = self.unrotated_size();
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => height,
+            Rotation::Deg90 | Rotation::Deg270 => width,
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        // Undo the rotation
+        let (width, height) = self.unrotated_size();
+        let (x, y) = match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (y, height - 1 - x),
+            Rotation::Deg180 => (width - 1 - x, height - 1 - y),
+            Rotation::Deg270 => (width - 1 - y, x),
+        };
+
+        // Find the panel
+        let (tile_row, mut tile_column) = (y / self.panel_height, x / self.panel_width);
+        if self.tile_wiring == Wiring::Serpentine && tile_row % 2 == 1 {
+            tile_column = self.tiles_x - 1 - tile_column;
+        }
+        let tile = tile_row * self.tiles_x + tile_column;
+
+        // Find the LED on the panel
+        let (mut x, mut y) = (x % self.panel_width, y % self.panel_height);
+        if let Corner::TopRight | Corner::BottomRight = self.origin {
+            x = self.panel_width - 1 - x;
+        }
+        if let Corner::BottomLeft | Corner::BottomRight = self.origin {
+            y = self.panel_height - 1 - y;
+        }
+        if self.wiring == Wiring::Serpentine && y % 2 == 1 {
+            x = self.panel_width - 1 - x;
+        }
+
+        Some(((tile * self.panel_height + y) * self.panel_width + x) as usize)
+    }
+}
@@ -0,0 +1,228 @@
+//! Driving several LED strips in parallel from multiple RMT channels.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{
+        Channel, RawChannelAccess, SingleShotTxTransaction, TxChannel, TxChannelCreator,
+        TxChannelInternal,
+    },
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::RGB8;
+
+use crate::{
+    configure_channel, encode_frame,
+    encoder::{BitPulses, Rgb8Encoder},
+    led_pulses_for_clock, reset_pulse_for_clock, wait_for_frame_gap, AdapterConfig,
+    LedAdapterError, PowerScale, RMT_RAM_ONE_LED,
+};
+
+/// Adapter taking several RMT channels and pins, refreshing all of the
+/// attached LED strips simultaneously.
+///
+/// All channels must be of the same type, and every strip gets its own buffer
+/// sized with [crate::buffer_size]. The total time of a refresh approaches the
+/// time of the longest strip rather than the sum of all strips.
+///
+/// For async adapters, see [join_write](crate::join_write).
+pub struct MultiStripAdapter<TX, const STRIPS: usize, const BUFFER_SIZE: usize>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    channels: [Option<Channel<Blocking, TX>>; STRIPS],
+    rmt_buffers: [[u32; BUFFER_SIZE]; STRIPS],
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame of every strip, applied to the next
+    power: [PowerScale; STRIPS],
+}
+
+impl<'d, TX, const STRIPS: usize, const BUFFER_SIZE: usize>
+    MultiStripAdapter<TX, STRIPS, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives each pin using the RMT channel
+    /// at the same position.
+    pub fn new<C, O>(
+        channels: [C; STRIPS],
+        pins: [O; STRIPS],
+        rmt_buffers: [[u32; BUFFER_SIZE]; STRIPS],
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channels, pins, rmt_buffers, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives each pin using the RMT channel
+    /// at the same position and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If a channel can't be configured, see
+    /// [MultiStripAdapter::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channels: [C; STRIPS],
+        pins: [O; STRIPS],
+        rmt_buffers: [[u32; BUFFER_SIZE]; STRIPS],
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channels, pins, rmt_buffers, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives each pin using the RMT channel
+    /// at the same position and the given configuration, returning an error
+    /// instead of panicking if a channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channels: [C; STRIPS],
+        pins: [O; STRIPS],
+        rmt_buffers: [[u32; BUFFER_SIZE]; STRIPS],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(
+                BUFFER_SIZE % RMT_RAM_ONE_LED == 1,
+                "BUFFER_SIZE must be sized with `buffer_size`"
+            )
+        };
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let mut configured = [const { None }; STRIPS];
+        for ((slot, channel), pin) in configured.iter_mut().zip(channels).zip(pins) {
+            *slot = Some(configure_channel(channel, pin, &config)?);
+        }
+
+        Ok(Self {
+            channels: configured,
+            rmt_buffers,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            last_frame_end: None,
+            power: [PowerScale::NONE; STRIPS],
+        })
+    }
+
+    /// Write one frame to every strip, transmitting all of them in parallel.
+    ///
+    /// All frames are encoded first, then all transmissions are started and
+    /// the channels are kept fed until every strip is done.
+    ///
+    /// The strips are handled independently: if a frame fails to encode or
+    /// transmit, only the result of that strip is an error, and all other
+    /// strips are still updated. The returned array holds the result for each
+    /// strip at the same position as its frame.
+    pub fn write_all<T, I>(&mut self, frames: [T; STRIPS]) -> [Result<(), LedAdapterError>; STRIPS]
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let mut results = [const { Ok(()) }; STRIPS];
+        // Number of pulse codes of the frame of each strip, including the end
+        // delimiter
+        let mut used = [0; STRIPS];
+
+        for ((((frame, buffer), power), result), used) in frames
+            .into_iter()
+            .zip(self.rmt_buffers.iter_mut())
+            .zip(self.power.iter_mut())
+            .zip(results.iter_mut())
+            .zip(used.iter_mut())
+        {
+            *result = encode_frame(
+                &mut Rgb8Encoder,
+                buffer,
+                frame,
+                &self.pulses,
+                &self.config,
+                power,
+                self.reset_pulse,
+            )
+            .map(|led_count| *used = led_count * RMT_RAM_ONE_LED + 1);
+        }
+
+        // Start all transmissions back to back
+        wait_for_frame_gap(self.last_frame_end, self.config.frame_gap_ns());
+        let mut transactions: [Option<SingleShotTxTransaction<'_, Channel<Blocking, TX>, u32>>;
+            STRIPS] = core::array::from_fn(|_| None);
+        for (strip, transaction) in transactions.iter_mut().enumerate() {
+            if results[strip].is_err() {
+                continue;
+            }
+
+            let Some(channel) = self.channels[strip].take() else {
+                results[strip] = Err(LedAdapterError::ChannelUnavailable);
+                continue;
+            };
+
+            match channel.transmit(&self.rmt_buffers[strip][..used[strip]]) {
+                Ok(t) => *transaction = Some(t),
+                Err(e) => results[strip] = Err(LedAdapterError::TransmissionError(e)),
+            }
+        }
+
+        // Keep polling every transaction so that none of the channels runs out
+        // of data while we're busy with another one.
+        loop {
+            let mut done = true;
+            for transaction in transactions.iter_mut().flatten() {
+                done &= transaction.poll();
+            }
+            if done {
+                break;
+            }
+        }
+
+        self.last_frame_end = Some(Instant::now());
+
+        for (strip, transaction) in transactions.into_iter().enumerate() {
+            let Some(transaction) = transaction else {
+                continue;
+            };
+
+            match transaction.wait() {
+                Ok(channel) => self.channels[strip] = Some(channel),
+                Err((e, channel)) => {
+                    self.channels[strip] = Some(channel);
+                    results[strip] = Err(LedAdapterError::TransmissionError(e));
+                }
+            }
+        }
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        for (strip, result) in results.iter().enumerate() {
+            if let Err(e) = result {
+                warn!("LED transmission on strip {} failed: {:?}", strip, e);
+            }
+        }
+
+        results
+    }
+
+    /// Write one frame to every strip, transmitting all of them in parallel.
+    ///
+    /// This is the same as [MultiStripAdapter::write_all], but only returns
+    /// the first error instead of the result for each strip.
+    pub fn write<T, I>(&mut self, frames: [T; STRIPS]) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        self.write_all(frames).into_iter().collect()
+    }
+}
@@ -0,0 +1,61 @@
+//! Support for clocked P9813 LEDs, e.g. Grove chainable RGB LEDs, driven by an
+//! SPI peripheral.
+
+use esp_hal::{spi::master::Spi, Blocking};
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+use crate::LedAdapterError;
+
+/// Adapter taking a blocking SPI driver and providing P9813 LED interaction
+/// functionality using the `smart-leds` crate
+///
+/// Connect the LED clock line to SCK and the data line to MOSI of the SPI
+/// driver.
+pub struct P9813Adapter<'d> {
+    spi: Spi<'d, Blocking>,
+}
+
+impl<'d> P9813Adapter<'d> {
+    /// Create a new adapter object that drives the LEDs using the SPI driver.
+    pub fn new(spi: Spi<'d, Blocking>) -> Self {
+        Self { spi }
+    }
+
+    /// Return the underlying SPI driver.
+    pub fn into_inner(self) -> Spi<'d, Blocking> {
+        self.spi
+    }
+}
+
+// The flag byte preceding the colors, holding two set bits followed by the
+// inverted two highest bits of blue, green and red as a checksum.
+fn flag_byte(color: RGB8) -> u8 {
+    0xc0 | ((!color.b >> 6) << 4) | (((!color.g >> 6) & 0x3) << 2) | ((!color.r >> 6) & 0x3)
+}
+
+impl SmartLedsWrite for P9813Adapter<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    /// Send the start frame, one frame per item of the iterator and the end
+    /// frame over SPI.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        // Start frame
+        self.spi.write(&[0x00; 4])?;
+
+        for item in iterator {
+            let color = item.into();
+            self.spi
+                .write(&[flag_byte(color), color.b, color.g, color.r])?;
+        }
+
+        // End frame
+        self.spi.write(&[0x00; 4])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Pacing of animations at a fixed frame rate with `embassy-time`.
+
+use embassy_time::{Duration, Ticker};
+use smart_leds_trait::{SmartLedsWriteAsync, RGB8};
+
+use crate::Framebuffer;
+
+/// Runs an animation at a fixed frame rate, writing each frame to the LEDs
+/// on a tick of an [embassy_time::Ticker].
+///
+/// [FramePacer::next_frame] waits for the next tick, sends the frame drawn
+/// since the last call and hands out the [Framebuffer] to draw the next one.
+/// As the frames are sent right after the tick, the time spent drawing
+/// doesn't affect the timing as long as it fits into a frame.
+///
+/// ```rust,ignore
+/// let mut pacer = FramePacer::<_, 64>::new(led, 60);
+/// let mut hue = 0u8;
+/// loop {
+///     let fb = pacer.next_frame().await.unwrap();
+///     fb.fill(hsv2rgb(Hsv { hue, sat: 255, val: 32 }));
+///     hue = hue.wrapping_add(1);
+/// }
+/// ```
+pub struct FramePacer<W, const N: usize> {
+    framebuffer: Framebuffer<W, N>,
+    ticker: Ticker,
+    // Whether the framebuffer was handed out and holds a frame to be sent
+    pending: bool,
+}
+
+impl<W, const N: usize> FramePacer<W, N> {
+    /// Create a pacer writing `fps` frames per second, with all LEDs turned
+    /// off.
+    ///
+    /// # Panics
+    ///
+    /// If `fps` is 0.
+    pub fn new(writer: W, fps: u64) -> Self {
+        assert!(fps > 0, "the frame rate must be at least 1 fps");
+        Self::with_ticker(writer, Ticker::every(Duration::from_hz(fps)))
+    }
+
+    /// Create a pacer writing a frame on every tick of `ticker`.
+    pub fn with_ticker(writer: W, ticker: Ticker) -> Self {
+        Self {
+            framebuffer: Framebuffer::new(writer),
+            ticker,
+            pending: false,
+        }
+    }
+
+    /// The framebuffer holding the frame drawn last.
+    pub fn framebuffer(&self) -> &Framebuffer<W, N> {
+        &self.framebuffer
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.framebuffer.into_inner()
+    }
+}
+
+impl<W, const N: usize> FramePacer<W, N>
+where
+    W: SmartLedsWriteAsync<Color = RGB8>,
+{
+    /// Send the frame drawn since the last call on the next tick, then return
+    /// the framebuffer to draw the following frame into.
+    ///
+    /// The first call returns right away and restarts the ticker, so the
+    /// first frame is sent one frame time later. If sending fails, the error
+    /// is returned and the frame is kept, to be sent again on the next call.
+    pub async fn next_frame(&mut self) -> Result<&mut Framebuffer<W, N>, W::Error> {
+        if self.pending {
+            self.ticker.next().await;
+            self.framebuffer.present_async().await?;
+        } else {
+            self.ticker.reset();
+            self.pending = true;
+        }
+
+        Ok(&mut self.framebuffer)
+    }
+}
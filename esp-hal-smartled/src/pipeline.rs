@@ -0,0 +1,274 @@
+//! Composable stages transforming the colors while the adapter encodes them.
+
+use smart_leds_trait::{RGB16, RGB8};
+
+use crate::{
+    cie1931_table,
+    encoder::{BitPulses, LedEncoder},
+    gamma_table, gamma_to_fixed, interpolate, scale_fine, AdapterConfig, TemporalDither,
+};
+
+/// A stage of a [ColorPipeline], transforming the color of the LED at
+/// `index`.
+///
+/// The channels are passed in 8.8 fixed point, i.e. the 8-bit value of a
+/// channel shifted left by 8, so stages can keep the precision lost by
+/// scaling for later stages, e.g. a [TemporalDither].
+///
+/// Tuples of up to six stages are stages themselves, applying their elements
+/// in order.
+pub trait ColorStage {
+    /// Transform the color of the LED at `index` of the frame.
+    fn apply(&mut self, index: usize, color: RGB16) -> RGB16;
+
+    /// The color [ColorStage::apply] would return, without changing the state
+    /// of the stage, to estimate the current drawn by a frame for
+    /// [AdapterConfig::with_power_limit].
+    ///
+    /// Defaults to the color unchanged, which overestimates stages that
+    /// darken the colors.
+    fn estimate(&self, color: RGB16) -> RGB16 {
+        color
+    }
+}
+
+/// Scales every channel by `brightness / 256`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brightness(pub u8);
+
+impl ColorStage for Brightness {
+    fn apply(&mut self, _index: usize, color: RGB16) -> RGB16 {
+        self.estimate(color)
+    }
+
+    fn estimate(&self, color: RGB16) -> RGB16 {
+        RGB16 {
+            r: scale_fine(color.r, self.0),
+            g: scale_fine(color.g, self.0),
+            b: scale_fine(color.b, self.0),
+        }
+    }
+}
+
+/// Scales the red, green and blue channels by the respective value of
+/// `correction / 256`, see [ColorCorrection](crate::ColorCorrection) and
+/// [ColorTemperature](crate::ColorTemperature) for typical values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Correction(pub RGB8);
+
+impl ColorStage for Correction {
+    fn apply(&mut self, _index: usize, color: RGB16) -> RGB16 {
+        self.estimate(color)
+    }
+
+    fn estimate(&self, color: RGB16) -> RGB16 {
+        RGB16 {
+            r: scale_fine(color.r, self.0.r),
+            g: scale_fine(color.g, self.0.g),
+            b: scale_fine(color.b, self.0.b),
+        }
+    }
+}
+
+/// Maps every channel through a transfer curve, interpolating between its
+/// entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Curve([u8; 256]);
+
+impl Curve {
+    /// A gamma correction with the given gamma value, clamped to between
+    /// 0.004 and 255.
+    pub const fn gamma(gamma: f32) -> Self {
+        Self(gamma_table(gamma_to_fixed(gamma)))
+    }
+
+    /// The CIE 1931 lightness curve.
+    pub const fn cie1931() -> Self {
+        Self(cie1931_table())
+    }
+
+    /// A custom curve given as lookup table.
+    pub const fn from_table(table: [u8; 256]) -> Self {
+        Self(table)
+    }
+}
+
+impl ColorStage for Curve {
+    fn apply(&mut self, _index: usize, color: RGB16) -> RGB16 {
+        self.estimate(color)
+    }
+
+    fn estimate(&self, color: RGB16) -> RGB16 {
+        RGB16 {
+            r: interpolate(color.r, &self.0),
+            g: interpolate(color.g, &self.0),
+            b: interpolate(color.b, &self.0),
+        }
+    }
+}
+
+/// Carries the part of every channel below 8 bits over to the same LED in the
+/// next frame, see [TemporalDither]. Usually the last stage.
+impl<const N_LEDS: usize> ColorStage for TemporalDither<N_LEDS> {
+    fn apply(&mut self, index: usize, color: RGB16) -> RGB16 {
+        let [r, g, b] = self.dither(index, [color.r, color.g, color.b]);
+        RGB16 {
+            r: (r as u16) << 8,
+            g: (g as u16) << 8,
+            b: (b as u16) << 8,
+        }
+    }
+}
+
+/// A custom stage calling the function with the index and color of every LED,
+/// rounded down to 8 bits per channel.
+pub struct Custom<F>(pub F);
+
+impl<F> ColorStage for Custom<F>
+where
+    F: FnMut(usize, RGB8) -> RGB8,
+{
+    fn apply(&mut self, index: usize, color: RGB16) -> RGB16 {
+        let color = (self.0)(
+            index,
+            RGB8 {
+                r: (color.r >> 8) as u8,
+                g: (color.g >> 8) as u8,
+                b: (color.b >> 8) as u8,
+            },
+        );
+        RGB16 {
+            r: (color.r as u16) << 8,
+            g: (color.g as u16) << 8,
+            b: (color.b as u16) << 8,
+        }
+    }
+}
+
+macro_rules! impl_color_stage_for_tuple {
+    ($($stage:ident),+) => {
+        impl<$($stage: ColorStage),+> ColorStage for ($($stage,)+) {
+            #[allow(non_snake_case)]
+            fn apply(&mut self, index: usize, color: RGB16) -> RGB16 {
+                let ($($stage,)+) = self;
+                $(let color = $stage.apply(index, color);)+
+                color
+            }
+
+            #[allow(non_snake_case)]
+            fn estimate(&self, color: RGB16) -> RGB16 {
+                let ($($stage,)+) = self;
+                $(let color = $stage.estimate(color);)+
+                color
+            }
+        }
+    };
+}
+
+impl_color_stage_for_tuple!(A);
+impl_color_stage_for_tuple!(A, B);
+impl_color_stage_for_tuple!(A, B, C);
+impl_color_stage_for_tuple!(A, B, C, D);
+impl_color_stage_for_tuple!(A, B, C, D, E);
+impl_color_stage_for_tuple!(A, B, C, D, E, F);
+
+/// Encoder of RGB colors with 8 bits per channel applying a [ColorStage],
+/// usually a tuple of stages, to every color while the adapter encodes it.
+///
+/// The stages replace the gamma correction, correction and color temperature
+/// of the configuration, which are ignored. Its brightness, which also
+/// carries [AdapterConfig::with_power_limit], is applied to the colors before
+/// the stages, and its color order after them.
+///
+/// ```rust,ignore
+/// let mut led = SmartLedsAdapterCustom::new(
+///     rmt.channel0,
+///     p.GPIO2,
+///     [0; buffer_size(NUM_LEDS)],
+///     ColorPipeline::new((
+///         Brightness(64),
+///         Correction(ColorCorrection::TypicalLedStrip.rgb()),
+///         Curve::gamma(2.2),
+///         TemporalDither::<NUM_LEDS>::new(),
+///     )),
+///     AdapterConfig::default(),
+/// );
+/// led.write(frame.iter().copied()).unwrap();
+/// ```
+pub struct ColorPipeline<S> {
+    stages: S,
+    // Index of the next LED of the frame being encoded.
+    index: usize,
+}
+
+impl<S> ColorPipeline<S>
+where
+    S: ColorStage,
+{
+    /// Create a pipeline applying `stages` in order.
+    pub const fn new(stages: S) -> Self {
+        Self { stages, index: 0 }
+    }
+
+    /// The stages of the pipeline.
+    pub fn stages(&self) -> &S {
+        &self.stages
+    }
+
+    /// Mutable access to the stages, e.g. to change the brightness.
+    pub fn stages_mut(&mut self) -> &mut S {
+        &mut self.stages
+    }
+
+    /// Release the stages.
+    pub fn into_inner(self) -> S {
+        self.stages
+    }
+}
+
+// The color in 8.8 fixed point, scaled by the brightness of the configuration.
+fn fine(color: RGB8, config: &AdapterConfig) -> RGB16 {
+    let channel = |value: u8| scale_fine((value as u16) << 8, config.brightness);
+    RGB16 {
+        r: channel(color.r),
+        g: channel(color.g),
+        b: channel(color.b),
+    }
+}
+
+// The channels of the color in 8.8 fixed point in the order they are sent,
+// rounded down to 8 bits.
+fn ordered(color: RGB16, config: &AdapterConfig) -> [u8; 3] {
+    let mut channels = config
+        .color_order
+        .order([color.r, color.g, color.b])
+        .map(|channel| (channel >> 8) as u8);
+    if config.clamp_first_channel {
+        channels[0] = channels[0].min(0xfe);
+    }
+    channels
+}
+
+impl<S> LedEncoder for ColorPipeline<S>
+where
+    S: ColorStage,
+{
+    type Color = RGB8;
+    const PULSES_PER_LED: usize = 24;
+
+    fn start_frame(&mut self) {
+        self.index = 0;
+    }
+
+    fn encode(&mut self, color: RGB8, pulses: &BitPulses, config: &AdapterConfig, out: &mut [u32]) {
+        let color = self.stages.apply(self.index, fine(color, config));
+        self.index += 1;
+
+        pulses.write_channels::<8>(ordered(color, config), out);
+    }
+
+    fn load(&self, color: &RGB8, config: &AdapterConfig) -> u32 {
+        let color = self.stages.estimate(fine(*color, config));
+        ordered(color, config).into_iter().map(u32::from).sum()
+    }
+}
@@ -0,0 +1,389 @@
+//! Pulse codes of the LED protocol.
+//!
+//! This module only depends on `core`, so the encoding can be tested on the
+//! host by the `host-tests` package next to the crate, which builds without
+//! esp-hal.
+
+const SK68XX_CODE_PERIOD: u32 = 1250; // 800kHz
+const SK68XX_T0H_NS: u32 = 400; // 300ns per SK6812 datasheet, 400 per WS2812. Some require >350ns for T0H. Others <500ns for T0H.
+const SK68XX_T0L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T0H_NS;
+const SK68XX_T1H_NS: u32 = 850; // 900ns per SK6812 datasheet, 850 per WS2812. > 550ns is sometimes enough. Some require T1H >= 2 * T0H. Some require > 300ns T1L.
+const SK68XX_T1L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T1H_NS;
+const SK68XX_RESET_NS: u32 = 80_000;
+
+const WS2811_SLOW_CODE_PERIOD: u32 = 2500; // 400kHz
+const WS2811_SLOW_T0H_NS: u32 = 500;
+const WS2811_SLOW_T0L_NS: u32 = WS2811_SLOW_CODE_PERIOD - WS2811_SLOW_T0H_NS;
+const WS2811_SLOW_T1H_NS: u32 = 1200;
+const WS2811_SLOW_T1L_NS: u32 = WS2811_SLOW_CODE_PERIOD - WS2811_SLOW_T1H_NS;
+const WS2811_SLOW_RESET_NS: u32 = 50_000;
+
+const WS2815_CODE_PERIOD: u32 = 1250; // 800kHz
+const WS2815_T0H_NS: u32 = 300; // 220ns to 380ns per datasheet
+const WS2815_T0L_NS: u32 = WS2815_CODE_PERIOD - WS2815_T0H_NS;
+const WS2815_T1H_NS: u32 = 950; // 580ns to 1600ns per datasheet
+const WS2815_T1L_NS: u32 = WS2815_CODE_PERIOD - WS2815_T1H_NS;
+const WS2815_RESET_NS: u32 = 280_000;
+
+const UCS1903_CODE_PERIOD: u32 = 2500; // 400kHz
+const UCS1903_T0H_NS: u32 = 500;
+const UCS1903_T0L_NS: u32 = UCS1903_CODE_PERIOD - UCS1903_T0H_NS;
+const UCS1903_T1H_NS: u32 = 2000;
+const UCS1903_T1L_NS: u32 = UCS1903_CODE_PERIOD - UCS1903_T1H_NS;
+const UCS1903_RESET_NS: u32 = 50_000;
+
+const TM1829_T0H_NS: u32 = 340;
+const TM1829_T0L_NS: u32 = 890;
+const TM1829_T1H_NS: u32 = 680;
+const TM1829_T1L_NS: u32 = 550;
+const TM1829_RESET_NS: u32 = 400_000;
+
+const TM1814_CODE_PERIOD: u32 = 1250; // 800kHz
+const TM1814_T0H_NS: u32 = 360;
+const TM1814_T0L_NS: u32 = TM1814_CODE_PERIOD - TM1814_T0H_NS;
+const TM1814_T1H_NS: u32 = 720;
+const TM1814_T1L_NS: u32 = TM1814_CODE_PERIOD - TM1814_T1H_NS;
+const TM1814_RESET_NS: u32 = 200_000;
+
+/// Pulse widths of the LED protocol, all in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LedTiming {
+    /// High time of a 0 bit.
+    pub t0h: u32,
+    /// Low time of a 0 bit.
+    pub t0l: u32,
+    /// High time of a 1 bit.
+    pub t1h: u32,
+    /// Low time of a 1 bit.
+    pub t1l: u32,
+    /// Low time at the end of a frame for the LEDs to latch it. This is
+    /// limited to 32767 ticks of the RMT clock, e.g. about 409 µs at 80 MHz.
+    pub reset: u32,
+}
+
+impl LedTiming {
+    /// Return these pulse widths with a different reset time, e.g. the 280 µs
+    /// needed by newer WS2812B chips.
+    pub const fn with_reset(mut self, reset: u32) -> Self {
+        self.reset = reset;
+        self
+    }
+
+    /// 800 kHz timing that works for SK68xx and WS2812 LEDs.
+    pub const SK68XX: LedTiming = LedTiming {
+        t0h: SK68XX_T0H_NS,
+        t0l: SK68XX_T0L_NS,
+        t1h: SK68XX_T1H_NS,
+        t1l: SK68XX_T1L_NS,
+        reset: SK68XX_RESET_NS,
+    };
+
+    /// 400 kHz timing of WS2811 LEDs running in slow mode.
+    pub const WS2811_SLOW: LedTiming = LedTiming {
+        t0h: WS2811_SLOW_T0H_NS,
+        t0l: WS2811_SLOW_T0L_NS,
+        t1h: WS2811_SLOW_T1H_NS,
+        t1l: WS2811_SLOW_T1L_NS,
+        reset: WS2811_SLOW_RESET_NS,
+    };
+
+    /// 800 kHz timing of WS2815 LEDs, which need a longer reset time than
+    /// WS2812 LEDs.
+    pub const WS2815: LedTiming = LedTiming {
+        t0h: WS2815_T0H_NS,
+        t0l: WS2815_T0L_NS,
+        t1h: WS2815_T1H_NS,
+        t1l: WS2815_T1L_NS,
+        reset: WS2815_RESET_NS,
+    };
+
+    /// 400 kHz timing of UCS1903 LEDs.
+    pub const UCS1903: LedTiming = LedTiming {
+        t0h: UCS1903_T0H_NS,
+        t0l: UCS1903_T0L_NS,
+        t1h: UCS1903_T1H_NS,
+        t1l: UCS1903_T1L_NS,
+        reset: UCS1903_RESET_NS,
+    };
+
+    /// 800 kHz timing of TM1829 LEDs. The line of these LEDs is inverted, see
+    /// [Chipset::Tm1829](crate::Chipset::Tm1829).
+    pub const TM1829: LedTiming = LedTiming {
+        t0h: TM1829_T0H_NS,
+        t0l: TM1829_T0L_NS,
+        t1h: TM1829_T1H_NS,
+        t1l: TM1829_T1L_NS,
+        reset: TM1829_RESET_NS,
+    };
+
+    /// 800 kHz timing of TM1814 LEDs. The line of these LEDs is inverted,
+    /// which the [Tm1814Adapter](crate::Tm1814Adapter) takes care of.
+    pub const TM1814: LedTiming = LedTiming {
+        t0h: TM1814_T0H_NS,
+        t0l: TM1814_T0L_NS,
+        t1h: TM1814_T1H_NS,
+        t1l: TM1814_T1L_NS,
+        reset: TM1814_RESET_NS,
+    };
+}
+
+/// Pulse codes of a 0 and a 1 bit, for the timing and polarity of the
+/// configuration the adapter was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BitPulses {
+    /// Pulse code of a 0 bit.
+    pub zero: u32,
+    /// Pulse code of a 1 bit.
+    pub one: u32,
+}
+
+impl BitPulses {
+    /// Write the `out.len()` least significant bits of `value`, most
+    /// significant bit first.
+    pub fn write_bits(&self, value: u32, out: &mut [u32]) {
+        let count = out.len();
+        for (bit, slot) in out.iter_mut().enumerate() {
+            *slot = match (value >> (count - 1 - bit)) & 1 {
+                0 => self.zero,
+                _ => self.one,
+            }
+        }
+    }
+
+    /// Write the `BITS` most significant bits of every channel, in the order
+    /// they are given.
+    pub(crate) fn write_channels<const BITS: usize>(&self, channels: [u8; 3], out: &mut [u32]) {
+        for (channel, out) in channels.into_iter().zip(out.chunks_exact_mut(BITS)) {
+            self.write_bits((channel >> (8 - BITS)) as u32, out);
+        }
+    }
+}
+
+// A pulse code sending `level1` for `length1` ticks, then `level2` for
+// `length2` ticks, in the layout of the RMT RAM.
+const fn pulse_code(level1: bool, length1: u16, level2: bool, length2: u16) -> u32 {
+    half_pulse(level1, length1) | half_pulse(level2, length2) << 16
+}
+
+// One half of a pulse code: the level in bit 15 and the length below it.
+const fn half_pulse(level: bool, length: u16) -> u32 {
+    (level as u32) << 15 | (length as u32 & 0x7fff)
+}
+
+// Number of ticks at `tick_hz` in `ns` nanoseconds, rounded down.
+const fn ns_to_ticks(ns: u32, tick_hz: u32) -> u64 {
+    ns as u64 * tick_hz as u64 / 1_000_000_000
+}
+
+/// Pulse codes of a 0 and a 1 bit. Every part of a pulse has to be at least
+/// one tick long, as a length of 0 ends the transmission, and fit into the 15
+/// bits of a pulse code, otherwise `None` is returned.
+pub(crate) fn bit_pulses(tick_hz: u32, timing: &LedTiming, inverted: bool) -> Option<BitPulses> {
+    let ticks = |ns| match ns_to_ticks(ns, tick_hz) {
+        ticks @ 1..=0x7fff => Some(ticks as u16),
+        _ => None,
+    };
+    // The high part of a pulse is sent low on an inverted line
+    let high = !inverted;
+    Some(BitPulses {
+        zero: pulse_code(high, ticks(timing.t0h)?, !high, ticks(timing.t0l)?),
+        one: pulse_code(high, ticks(timing.t1h)?, !high, ticks(timing.t1l)?),
+    })
+}
+
+/// The end delimiter, holding the line low for the reset time of the LEDs. A
+/// zero length in the second half of a pulse code ends the transmission.
+pub(crate) fn reset_pulse(tick_hz: u32, timing: &LedTiming, inverted: bool) -> u32 {
+    let ticks = ns_to_ticks(timing.reset, tick_hz);
+    pulse_code(inverted, ticks.min(0x7fff) as u16, inverted, 0)
+}
+
+/// Whether either half of the pulse code has a length of zero, which ends the
+/// transmission.
+pub(crate) const fn is_end_marker(code: u32) -> bool {
+    code & 0x7fff == 0 || (code >> 16) & 0x7fff == 0
+}
+
+/// Number of ticks the pulse code is sent for, up to a half of zero length.
+pub(crate) const fn pulse_code_ticks(code: u32) -> u64 {
+    let first = code & 0x7fff;
+    if first == 0 {
+        0
+    } else {
+        (first + ((code >> 16) & 0x7fff)) as u64
+    }
+}
+
+/// Encodes the channels of every LED, in the order they are sent, followed
+/// by the end delimiter into `buffer`, and returns the number of LEDs.
+///
+/// If the LEDs don't fit, the number of LEDs encoded before running out of
+/// space is returned as the error.
+pub(crate) fn encode_channels(
+    leds: impl IntoIterator<Item = [u8; 3]>,
+    pulses: &BitPulses,
+    reset_pulse: u32,
+    buffer: &mut [u32],
+) -> Result<usize, usize> {
+    let mut led_count = 0;
+    for channels in leds {
+        // Leave a slot for the end delimiter
+        let start = led_count * 24;
+        let out = buffer.get_mut(start..start + 25).ok_or(led_count)?;
+        pulses.write_channels::<8>(channels, &mut out[..24]);
+        led_count += 1;
+    }
+
+    *buffer.get_mut(led_count * 24).ok_or(led_count)? = reset_pulse;
+
+    Ok(led_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 100 ns per tick
+    const TICK_HZ: u32 = 10_000_000;
+
+    const TIMING: LedTiming = LedTiming {
+        t0h: 400,
+        t0l: 800,
+        t1h: 800,
+        t1l: 400,
+        reset: 50_000,
+    };
+
+    // Pulse code driving the line high for `high` ticks, then low for `low`.
+    const fn pulse(high: u32, low: u32) -> u32 {
+        high | 1 << 15 | low << 16
+    }
+
+    const ZERO: u32 = pulse(4, 8);
+    const ONE: u32 = pulse(8, 4);
+
+    fn bits(value: u8) -> [u32; 8] {
+        core::array::from_fn(|bit| match value & (0x80 >> bit) {
+            0 => ZERO,
+            _ => ONE,
+        })
+    }
+
+    fn encode(leds: &[[u8; 3]], buffer: &mut [u32]) -> Result<usize, usize> {
+        let pulses = bit_pulses(TICK_HZ, &TIMING, false).unwrap();
+        let reset_pulse = reset_pulse(TICK_HZ, &TIMING, false);
+        encode_channels(leds.iter().copied(), &pulses, reset_pulse, buffer)
+    }
+
+    #[test]
+    fn converts_pulse_widths_to_ticks() {
+        assert_eq!(
+            bit_pulses(TICK_HZ, &TIMING, false),
+            Some(BitPulses {
+                zero: ZERO,
+                one: ONE
+            })
+        );
+    }
+
+    #[test]
+    fn swaps_levels_of_inverted_line() {
+        let pulses = bit_pulses(TICK_HZ, &TIMING, true).unwrap();
+
+        assert_eq!(pulses.zero, 4 | 8 << 16 | 1 << 31);
+        assert_eq!(reset_pulse(TICK_HZ, &TIMING, true), 500 | 1 << 15 | 1 << 31);
+    }
+
+    #[test]
+    fn encodes_most_significant_bit_first() {
+        let mut buffer = [0; 2 * 24 + 1];
+
+        let led_count = encode(&[[0x34, 0x12, 0x56], [0x00, 0xff, 0x80]], &mut buffer);
+
+        assert_eq!(led_count, Ok(2));
+        let expected = [0x34, 0x12, 0x56, 0x00, 0xff, 0x80].map(bits);
+        for (channel, expected) in buffer.chunks_exact(8).zip(expected) {
+            assert_eq!(channel, expected);
+        }
+    }
+
+    #[test]
+    fn ends_with_reset_pulse() {
+        let mut buffer = [ONE; 24 + 2];
+
+        encode(&[[0; 3]], &mut buffer).unwrap();
+
+        // Low for 500 ticks, then an end marker
+        assert_eq!(buffer[24], 500);
+        assert!(is_end_marker(buffer[24]));
+        assert_eq!(pulse_code_ticks(buffer[24]), 500);
+        // Past the frame, the buffer is left as it is
+        assert_eq!(buffer[25], ONE);
+    }
+
+    #[test]
+    fn rejects_more_leds_than_the_buffer_holds() {
+        let mut buffer = [0; 24 + 1];
+
+        assert_eq!(encode(&[[0; 3]; 2], &mut buffer), Err(1));
+    }
+
+    #[test]
+    fn rejects_pulses_shorter_than_a_tick() {
+        // 1 µs per tick
+        assert_eq!(bit_pulses(1_000_000, &TIMING, false), None);
+    }
+
+    #[test]
+    fn rejects_pulses_longer_than_a_pulse_code() {
+        // 32768 ticks
+        let timing = LedTiming {
+            t1l: 3_276_800,
+            ..TIMING
+        };
+
+        assert_eq!(bit_pulses(TICK_HZ, &timing, false), None);
+    }
+
+    // The encoding of a channel before the bit depth was configurable.
+    fn reference_channel(value: u8) -> [u32; 8] {
+        [128, 64, 32, 16, 8, 4, 2, 1].map(|position| match value & position {
+            0 => ZERO,
+            _ => ONE,
+        })
+    }
+
+    #[test]
+    fn default_bit_depth_matches_reference() {
+        let pulses = BitPulses {
+            zero: ZERO,
+            one: ONE,
+        };
+        for value in 0..=255u8 {
+            let channels = [value, !value, value.rotate_left(3)];
+            let mut out = [0; 24];
+            pulses.write_channels::<8>(channels, &mut out);
+
+            for (out, channel) in out.chunks_exact(8).zip(channels) {
+                assert_eq!(out, reference_channel(channel));
+            }
+        }
+    }
+
+    #[test]
+    fn lower_bit_depth_sends_most_significant_bits() {
+        let pulses = BitPulses {
+            zero: ZERO,
+            one: ONE,
+        };
+        let mut out = [0; 12];
+        pulses.write_channels::<4>([0x3c, 0xa5, 0xff], &mut out);
+
+        let expected = [0x3, 0xa, 0xf].map(|value: u8| reference_channel(value << 4));
+        for (channel, expected) in out.chunks_exact(4).zip(expected) {
+            assert_eq!(channel, &expected[..4]);
+        }
+    }
+}
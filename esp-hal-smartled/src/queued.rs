@@ -0,0 +1,449 @@
+//! Adapter queueing several frames to be transmitted back to back.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{
+        Channel, RawChannelAccess, SingleShotTxTransaction, TxChannel, TxChannelCreator,
+        TxChannelInternal,
+    },
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::SmartLedsWrite;
+
+use crate::{
+    configure_channel, encode_frame,
+    encoder::{BitPulses, LedEncoder, Rgb8Encoder},
+    frame_gap_elapsed, led_pulses_for_clock, reset_pulse_for_clock, AdapterConfig, LedAdapterError,
+    PowerScale,
+};
+
+/// State of the RMT channel of the adapter.
+enum TxState<TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// No frame is being transmitted.
+    Idle(Channel<Blocking, TX>),
+    /// A frame is being transmitted.
+    InFlight(SingleShotTxTransaction<'static, Channel<Blocking, TX>, u32>),
+    /// The channel was lost in a failed transmission.
+    Lost,
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing RGB LED
+/// interaction functionality using the `smart-leds` crate, with a queue of
+/// `FRAMES` RMT buffers.
+///
+/// [SmartLedsWrite::write] encodes the frame into a free buffer and returns
+/// right away, only waiting if the queue is full. The queued frames are sent
+/// one after the other, each followed by the reset time of the LEDs and at
+/// least [AdapterConfig::min_frame_gap_ns], which smooths out irregular
+/// animation computation times.
+///
+/// The RMT driver only makes progress while the adapter is in use, so
+/// [SmartLedsAdapterQueued::poll] has to be called regularly to start the next
+/// frame, and more often than the channel takes to send half of its RAM for
+/// frames that don't fit into it (see [AdapterConfig::with_memsize]).
+///
+/// With two frames, the adapter double buffers: the next frame is encoded
+/// while the previous one is sent.
+///
+/// The colors are converted by the [LedEncoder] `E`, see
+/// [SmartLedsAdapterQueued::try_new_with_encoder] to drive other chips.
+pub struct SmartLedsAdapterQueued<
+    TX,
+    const BUFFER_SIZE: usize,
+    const FRAMES: usize,
+    E = Rgb8Encoder,
+> where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    state: TxState<TX>,
+    rmt_buffers: FrameBuffers<BUFFER_SIZE, FRAMES>,
+    // Index of the oldest frame, which is the one in flight if any.
+    first: usize,
+    // Number of frames queued, including the one in flight.
+    len: usize,
+    encoder: E,
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+    frame_done: Option<fn(&mut Self)>,
+}
+
+/// The buffers of the queue, which are only accessed through a raw pointer,
+/// as one of them may be borrowed by the transaction in flight while the
+/// others are written.
+struct FrameBuffers<const BUFFER_SIZE: usize, const FRAMES: usize> {
+    // Points to the buffers exclusively borrowed for `'static` by the
+    // constructor.
+    buffers: *mut [u32; BUFFER_SIZE],
+    // Number of pulse codes of the frame in each buffer, including its end
+    // delimiter.
+    used: [usize; FRAMES],
+}
+
+// SAFETY: The pointer stands in for the `&'static mut` to the buffers given
+// to the constructor, which is `Send` as the buffers only hold `u32`s. The
+// only other references into them are the shared one of the transaction in
+// flight, which is kept in the same adapter, and the mutable ones taken for
+// encoding, which don't outlive a call. Moving the adapter to another context
+// therefore moves every access to the buffers along with it.
+unsafe impl<const BUFFER_SIZE: usize, const FRAMES: usize> Send
+    for FrameBuffers<BUFFER_SIZE, FRAMES>
+{
+}
+
+impl<const BUFFER_SIZE: usize, const FRAMES: usize> FrameBuffers<BUFFER_SIZE, FRAMES> {
+    fn new(buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES]) -> Self {
+        Self {
+            buffers: buffers.as_mut_ptr(),
+            used: [0; FRAMES],
+        }
+    }
+
+    /// The buffer at `index` to encode a frame into.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must not be borrowed by the transaction in flight.
+    unsafe fn get_mut(&mut self, index: usize) -> &mut [u32; BUFFER_SIZE] {
+        // SAFETY: The index is in bounds of the `FRAMES` buffers, and the
+        // caller guarantees that nothing else borrows the buffer.
+        unsafe { &mut *self.buffers.add(index % FRAMES) }
+    }
+
+    /// The frame in the buffer at `index`, to be transmitted.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must not be written again while the returned reference is
+    /// alive.
+    unsafe fn frame(&self, index: usize) -> &'static [u32] {
+        let index = index % FRAMES;
+        // SAFETY: The buffers are borrowed for `'static` and the index is in
+        // bounds, and the caller guarantees that they aren't written while
+        // the reference is alive.
+        let buffer: &'static [u32; BUFFER_SIZE] = unsafe { &*self.buffers.add(index) };
+        &buffer[..self.used[index]]
+    }
+}
+
+impl<'d, TX, const BUFFER_SIZE: usize, const FRAMES: usize>
+    SmartLedsAdapterQueued<TX, BUFFER_SIZE, FRAMES>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the buffers, each sized with [buffer_size](crate::buffer_size).
+    pub fn new<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES],
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channel, pin, rmt_buffers, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the buffers and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterQueued::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES],
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffers, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the buffers and the given configuration, returning an error instead of
+    /// panicking if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_encoder(channel, pin, rmt_buffers, Rgb8Encoder, config)
+    }
+}
+
+impl<'d, TX, const BUFFER_SIZE: usize, const FRAMES: usize, E>
+    SmartLedsAdapterQueued<TX, BUFFER_SIZE, FRAMES, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the buffers, the encoder and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterQueued::try_new_with_encoder].
+    pub fn new_with_encoder<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES],
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_encoder(channel, pin, rmt_buffers, encoder, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the buffers, each sized with
+    /// [buffer_size_custom](crate::buffer_size_custom) for the encoder, and
+    /// the given configuration, returning an error instead of panicking if
+    /// the channel can't be configured.
+    pub fn try_new_with_encoder<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffers: &'static mut [[u32; BUFFER_SIZE]; FRAMES],
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(E::PULSES_PER_LED > 0, "PULSES_PER_LED must not be 0");
+            assert!(
+                BUFFER_SIZE % E::PULSES_PER_LED == 1 % E::PULSES_PER_LED && BUFFER_SIZE > 0,
+                "BUFFER_SIZE must be sized with `buffer_size` or `buffer_size_custom`"
+            );
+            assert!(FRAMES >= 2, "FRAMES must be at least 2");
+        };
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            state: TxState::Idle(channel),
+            rmt_buffers: FrameBuffers::new(rmt_buffers),
+            first: 0,
+            len: 0,
+            encoder,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            last_frame_end: None,
+            power: PowerScale::NONE,
+            frame_done: None,
+        })
+    }
+
+    /// The encoder of the adapter.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Mutable access to the encoder, e.g. to change its settings.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Number of frames waiting to be sent, including the one in flight.
+    pub fn queued(&self) -> usize {
+        self.len
+    }
+
+    /// Set a function called by [SmartLedsAdapterQueued::poll] whenever a
+    /// frame has been sent, e.g. to queue the next one.
+    ///
+    /// Together with calling `poll` from a periodic timer interrupt, this
+    /// refreshes the LEDs at a fixed rate even while the main loop is busy.
+    /// Blocking RMT channels don't raise interrupts, so the hook is only
+    /// called from `poll`; to be notified by the RMT interrupt itself, use
+    /// [SmartLedsAdapterAsync::start_write](crate::SmartLedsAdapterAsync::start_write).
+    pub fn set_frame_done_hook(&mut self, hook: Option<fn(&mut Self)>) {
+        self.frame_done = hook;
+    }
+
+    /// Keep the frame in flight fed, and start the next queued frame once it
+    /// is done.
+    ///
+    /// An error means the frame in flight failed; it is dropped from the
+    /// queue and the following frames are still sent.
+    pub fn poll(&mut self) -> Result<(), LedAdapterError> {
+        let mut result = Ok(());
+
+        if let TxState::InFlight(transaction) = &mut self.state {
+            if !transaction.poll() {
+                return Ok(());
+            }
+        }
+
+        match core::mem::replace(&mut self.state, TxState::Lost) {
+            TxState::InFlight(transaction) => {
+                match transaction.wait() {
+                    Ok(channel) => self.state = TxState::Idle(channel),
+                    Err((e, channel)) => {
+                        self.state = TxState::Idle(channel);
+                        result = Err(LedAdapterError::TransmissionError(e));
+                    }
+                }
+                self.last_frame_end = Some(Instant::now());
+                self.first = (self.first + 1) % FRAMES;
+                self.len -= 1;
+
+                if let Some(frame_done) = self.frame_done {
+                    frame_done(self);
+                }
+            }
+            state => self.state = state,
+        }
+
+        if self.len > 0 && frame_gap_elapsed(self.last_frame_end, self.config.frame_gap_ns()) {
+            result = result.and(self.start_next());
+        }
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+
+    /// Wait until all queued frames have been sent.
+    ///
+    /// Returns the last error of the frames sent in the meantime.
+    pub fn flush(&mut self) -> Result<(), LedAdapterError> {
+        let mut result = Ok(());
+        while self.len > 0 {
+            if let Err(e) = self.poll() {
+                result = Err(e);
+            }
+            if let TxState::Lost = self.state {
+                return Err(LedAdapterError::ChannelUnavailable);
+            }
+        }
+
+        result
+    }
+
+    /// Give up the adapter and return the configured RMT channel, waiting for
+    /// all queued frames to be sent.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        // Errors of the queued frames don't affect the channel
+        self.flush().ok();
+        match core::mem::replace(&mut self.state, TxState::Lost) {
+            TxState::Idle(channel) => Ok(channel),
+            _ => Err(LedAdapterError::ChannelUnavailable),
+        }
+    }
+
+    /// Start transmitting the oldest queued frame, the channel must be idle.
+    fn start_next(&mut self) -> Result<(), LedAdapterError> {
+        let channel = match core::mem::replace(&mut self.state, TxState::Lost) {
+            TxState::Idle(channel) => channel,
+            state => {
+                self.state = state;
+                return Ok(());
+            }
+        };
+
+        // SAFETY: The buffer isn't written again before the transaction is
+        // finished, as it is only freed once the transaction is done.
+        let frame = unsafe { self.rmt_buffers.frame(self.first) };
+        match channel.transmit(frame) {
+            Ok(transaction) => {
+                self.state = TxState::InFlight(transaction);
+                Ok(())
+            }
+            Err(e) => {
+                // The channel is lost, so drop all queued frames
+                self.len = 0;
+                Err(LedAdapterError::TransmissionError(e))
+            }
+        }
+    }
+}
+
+impl<TX, const BUFFER_SIZE: usize, const FRAMES: usize, E> SmartLedsWrite
+    for SmartLedsAdapterQueued<TX, BUFFER_SIZE, FRAMES, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    type Error = LedAdapterError;
+    type Color = E::Color;
+
+    /// Convert all items of the iterator to the RMT format and add them
+    /// to a free buffer, waiting for one if the queue is full, then queue the
+    /// frame for transmission.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        while self.len == FRAMES {
+            if let TxState::Lost = self.state {
+                return Err(LedAdapterError::ChannelUnavailable);
+            }
+            // Errors of earlier frames were already reported by `poll`
+            self.poll().ok();
+        }
+        if let TxState::Lost = self.state {
+            return Err(LedAdapterError::ChannelUnavailable);
+        }
+
+        // SAFETY: The free buffer isn't borrowed by the transaction in flight,
+        // which sends the oldest queued frame.
+        let index = (self.first + self.len) % FRAMES;
+        let buffer = unsafe { self.rmt_buffers.get_mut(index) };
+
+        let state = &mut self.state;
+        let led_count = encode_frame(
+            &mut self.encoder,
+            buffer,
+            iterator.into_iter().inspect(|_| {
+                // Keep the frame in flight fed while encoding
+                if let TxState::InFlight(transaction) = state {
+                    transaction.poll();
+                }
+            }),
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )?;
+        self.rmt_buffers.used[index] = led_count * E::PULSES_PER_LED + 1;
+
+        self.len += 1;
+
+        self.poll()
+    }
+}
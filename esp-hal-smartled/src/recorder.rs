@@ -0,0 +1,112 @@
+//! Writer wrapper remembering the last frame written.
+
+use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB8};
+
+/// Smart LED writer keeping a copy of the last frame of up to `N` colors
+/// written through it, e.g. to resume an animation after a settings screen
+/// or to freeze the current frame.
+///
+/// The copy costs `3 * N` bytes of RAM, so it is only kept by wrapping the
+/// adapter. Colors are recorded before any brightness, correction or gamma of
+/// the adapter is applied, and colors beyond the first `N` are sent but not
+/// recorded. A failed write still replaces the recorded frame.
+///
+/// ```rust,ignore
+/// let mut led = FrameRecorder::<_, 64>::new(led);
+/// led.write(frame.iter().copied()).unwrap();
+/// let frozen: [RGB8; 64] = led.last_frame().try_into().unwrap();
+/// ```
+pub struct FrameRecorder<W, const N: usize> {
+    writer: W,
+    frame: [RGB8; N],
+    len: usize,
+}
+
+impl<W, const N: usize> FrameRecorder<W, N> {
+    /// Create a new recorder in front of `writer`, with an empty last frame.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            frame: [RGB8::default(); N],
+            len: 0,
+        }
+    }
+
+    /// The colors of the last frame written, at most `N` of them.
+    pub fn last_frame(&self) -> &[RGB8] {
+        &self.frame[..self.len]
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, const N: usize> FrameRecorder<W, N>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    /// Send the last frame again, e.g. after the LEDs lost power.
+    pub fn rewrite(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.frame[..self.len].iter().copied())
+    }
+}
+
+/// Record the colors of `iterator` into `frame` while passing them on, and
+/// store the number of recorded colors in `len`.
+fn record<'a, T, I, const N: usize>(
+    iterator: T,
+    frame: &'a mut [RGB8; N],
+    len: &'a mut usize,
+) -> impl Iterator<Item = RGB8> + 'a
+where
+    T: IntoIterator<Item = I>,
+    T::IntoIter: 'a,
+    I: Into<RGB8>,
+{
+    *len = 0;
+    iterator.into_iter().map(move |color| {
+        let color = color.into();
+        if let Some(slot) = frame.get_mut(*len) {
+            *slot = color;
+            *len += 1;
+        }
+        color
+    })
+}
+
+impl<W, const N: usize> SmartLedsWrite for FrameRecorder<W, N>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    type Error = W::Error;
+    type Color = RGB8;
+
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        self.writer
+            .write(record(iterator, &mut self.frame, &mut self.len))
+    }
+}
+
+impl<W, const N: usize> SmartLedsWriteAsync for FrameRecorder<W, N>
+where
+    W: SmartLedsWriteAsync<Color = RGB8>,
+{
+    type Error = W::Error;
+    type Color = RGB8;
+
+    async fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        self.writer
+            .write(record(iterator, &mut self.frame, &mut self.len))
+            .await
+    }
+}
@@ -0,0 +1,96 @@
+//! A renderer owning an LED writer and taking commands from an
+//! `embassy-sync` channel.
+
+use core::fmt::Debug;
+
+use embassy_sync::{blocking_mutex::raw::RawMutex, channel::Receiver};
+use smart_leds_trait::{SmartLedsWriteAsync, RGB8};
+
+use crate::scale;
+
+/// Command for [run_renderer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderCommand<const N: usize> {
+    /// Show a whole frame of `N` colors.
+    Frame([RGB8; N]),
+    /// Set all LEDs to the same color.
+    Fill(RGB8),
+    /// Set the color of one LED, ignored if `index` is out of bounds.
+    SetPixel(usize, RGB8),
+    /// Turn all LEDs off.
+    Clear,
+    /// Scale all colors by `(brightness + 1) / 256` from now on, so 255
+    /// keeps them unchanged.
+    SetBrightness(u8),
+}
+
+/// Owns the writer of `N` LEDs and applies the commands received from the
+/// channel, so several tasks can update the LEDs without sharing the writer
+/// behind a mutex.
+///
+/// Commands that queued up while a frame was sent are applied together, and
+/// only the resulting frame is sent. A frame that fails to send is logged
+/// with `defmt` or `log` and dropped, the next command sends a fresh one.
+/// This never returns; task functions can't be generic, so it is meant to be
+/// called from a task of the application:
+///
+/// ```rust,ignore
+/// static COMMANDS: Channel<CriticalSectionRawMutex, RenderCommand<64>, 4> = Channel::new();
+///
+/// #[embassy_executor::task]
+/// async fn renderer(led: SmartLedsAdapterAsync<ChannelTx, { buffer_size_async(64) }>) {
+///     run_renderer(led, COMMANDS.receiver()).await
+/// }
+///
+/// // Anywhere else
+/// COMMANDS.send(RenderCommand::Fill(RED)).await;
+/// ```
+pub async fn run_renderer<M, W, const N: usize, const CAP: usize>(
+    mut writer: W,
+    receiver: Receiver<'_, M, RenderCommand<N>, CAP>,
+) where
+    M: RawMutex,
+    W: SmartLedsWriteAsync<Color = RGB8>,
+    W::Error: Debug,
+{
+    let mut pixels = [RGB8::default(); N];
+    let mut brightness = 255;
+
+    loop {
+        let mut command = receiver.receive().await;
+        loop {
+            match command {
+                RenderCommand::Frame(frame) => pixels = frame,
+                RenderCommand::Fill(color) => pixels = [color; N],
+                RenderCommand::SetPixel(index, color) => {
+                    if let Some(pixel) = pixels.get_mut(index) {
+                        *pixel = color;
+                    }
+                }
+                RenderCommand::Clear => pixels = [RGB8::default(); N],
+                RenderCommand::SetBrightness(value) => brightness = value,
+            }
+
+            match receiver.try_receive() {
+                Ok(next) => command = next,
+                Err(_) => break,
+            }
+        }
+
+        let colors = pixels.iter().map(|color| RGB8 {
+            r: scale(color.r, brightness),
+            g: scale(color.g, brightness),
+            b: scale(color.b, brightness),
+        });
+        // Errors can't be returned to anyone, the next command sends a new frame
+        if let Err(_error) = writer.write(colors).await {
+            #[cfg(feature = "defmt")]
+            warn!(
+                "Renderer failed to send a frame: {:?}",
+                defmt::Debug2Format(&_error)
+            );
+            #[cfg(not(feature = "defmt"))]
+            warn!("Renderer failed to send a frame: {:?}", _error);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Support for RGBW LEDs such as the SK6812 RGBW, which have an additional
+//! white channel.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{Channel, RawChannelAccess, TxChannelCreator, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::{SmartLedsWrite, White, RGB8, RGBW};
+
+use crate::{
+    configure_channel, encode_frame,
+    encoder::{BitPulses, LedEncoder, RgbwEncoder},
+    led_pulses_for_clock, reset_pulse_for_clock, AdapterConfig, ChannelState, Chipset,
+    ColorTemperature, LedAdapterError, PowerScale,
+};
+
+// Required RMT RAM to drive one LED.
+const RMT_RAM_ONE_RGBW_LED: usize = RgbwEncoder::PULSES_PER_LED;
+
+/// Function to calculate the required RMT buffer size for a given number of
+/// RGBW LEDs when using the blocking API.
+pub const fn buffer_size_rgbw(num_leds: usize) -> usize {
+    // 1 additional pulse for the end delimiter
+    num_leds * RMT_RAM_ONE_RGBW_LED + 1
+}
+
+/// Macro to allocate a buffer sized for a specific number of RGBW LEDs to be
+/// addressed.
+///
+/// Attempting to use more LEDs that the buffer is configured for will result in
+/// an `LedAdapterError::BufferSizeExceeded` error.
+#[macro_export]
+macro_rules! smart_led_buffer_rgbw {
+    ( $num_leds: expr ) => {
+        [0u32; $crate::buffer_size_rgbw($num_leds)]
+    };
+}
+
+/// How [SmartLedsAdapterRgbw::write_rgb] derives the white channel from RGB
+/// colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WhiteMode {
+    /// Leave the white channel off.
+    #[default]
+    Off,
+    /// Drive the white channel with the common part of the color channels,
+    /// i.e. `min(r, g, b)`, and subtract it from them.
+    Extract,
+}
+
+impl WhiteMode {
+    /// Convert an RGB color to RGBW using this mode.
+    pub fn convert(&self, color: RGB8) -> RGBW<u8> {
+        let RGB8 { r, g, b } = color;
+        let w = match self {
+            WhiteMode::Off => 0,
+            WhiteMode::Extract => r.min(g).min(b),
+        };
+
+        RGBW {
+            r: r - w,
+            g: g - w,
+            b: b - w,
+            a: White(w),
+        }
+    }
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing RGBW LED
+/// interaction functionality using the `smart-leds` crate
+///
+/// The colors are converted by the [RgbwEncoder].
+pub struct SmartLedsAdapterRgbw<TX, const BUFFER_SIZE: usize>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    channel: ChannelState<TX>,
+    rmt_buffer: [u32; BUFFER_SIZE],
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    white_mode: WhiteMode,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+}
+
+impl<'d, TX, const BUFFER_SIZE: usize> SmartLedsAdapterRgbw<TX, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel.
+    pub fn new<C, O>(channel: C, pin: O, rmt_buffer: [u32; BUFFER_SIZE]) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the configuration of the given chip.
+    ///
+    /// # Panics
+    ///
+    /// If the chip has no white channel, see [Chipset::channels], is a
+    /// TM1814, which needs the [Tm1814Adapter](crate::Tm1814Adapter), or the
+    /// channel can't be configured, see
+    /// [SmartLedsAdapterRgbw::try_new_with_chipset].
+    pub fn new_with_chipset<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        chipset: Chipset,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_chipset(channel, pin, rmt_buffer, chipset).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the configuration of the given chip, returning an error instead of
+    /// panicking if the chip isn't supported or the channel can't be
+    /// configured.
+    pub fn try_new_with_chipset<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        chipset: Chipset,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        if chipset.channels() != 4 || chipset == Chipset::Tm1814 {
+            return Err(LedAdapterError::UnsupportedChipset(chipset));
+        }
+        Self::try_new_with_config(channel, pin, rmt_buffer, chipset.config())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterRgbw::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffer, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, returning an error instead of panicking
+    /// if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(
+                BUFFER_SIZE % RMT_RAM_ONE_RGBW_LED == 1,
+                "BUFFER_SIZE must be sized with `buffer_size_rgbw`"
+            )
+        };
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            channel: ChannelState::Ready(channel),
+            rmt_buffer,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            white_mode: WhiteMode::Off,
+            last_frame_end: None,
+            power: PowerScale::NONE,
+        })
+    }
+
+    /// Set how [SmartLedsAdapterRgbw::write_rgb] derives the white channel.
+    pub fn with_white_mode(mut self, white_mode: WhiteMode) -> Self {
+        self.white_mode = white_mode;
+        self
+    }
+
+    /// Set how [SmartLedsAdapterRgbw::write_rgb] derives the white channel.
+    pub fn set_white_mode(&mut self, white_mode: WhiteMode) {
+        self.white_mode = white_mode;
+    }
+
+    /// How [SmartLedsAdapterRgbw::write_rgb] derives the white channel.
+    pub fn white_mode(&self) -> WhiteMode {
+        self.white_mode
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+
+    /// Scale all colors written from now on by `brightness / 256`, see
+    /// [AdapterConfig::with_brightness].
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.config = self.config.with_brightness(brightness);
+    }
+
+    /// The brightness all colors are scaled with.
+    pub fn brightness(&self) -> u8 {
+        self.config.brightness()
+    }
+
+    /// Scale the red, green and blue channels of all colors written from now
+    /// on, see [AdapterConfig::with_correction].
+    pub fn set_correction(&mut self, correction: RGB8) {
+        self.config = self.config.with_correction(correction);
+    }
+
+    /// Tint all colors written from now on to the color of a light source,
+    /// see [AdapterConfig::with_color_temperature].
+    pub fn set_color_temperature(&mut self, color_temperature: ColorTemperature) {
+        self.config = self.config.with_color_temperature(color_temperature);
+    }
+
+    /// Write RGB colors, deriving the white channel according to the
+    /// configured [WhiteMode].
+    ///
+    /// This lets effects written for RGB LEDs run unmodified on RGBW LEDs.
+    pub fn write_rgb<T, I>(&mut self, iterator: T) -> Result<(), LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let white_mode = self.white_mode;
+        self.write(
+            iterator
+                .into_iter()
+                .map(|color| white_mode.convert(color.into())),
+        )
+    }
+}
+
+impl<TX, const BUFFER_SIZE: usize> SmartLedsWrite for SmartLedsAdapterRgbw<TX, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    type Error = LedAdapterError;
+    type Color = RGBW<u8>;
+
+    /// Convert all RGBW items of the iterator to the RMT format and
+    /// add them to internal buffer, then start a singular RMT operation
+    /// based on that buffer.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let led_count = encode_frame(
+            &mut RgbwEncoder,
+            &mut self.rmt_buffer,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )?;
+
+        let used = led_count * RMT_RAM_ONE_RGBW_LED + 1;
+        self.channel.send_frame(
+            &self.rmt_buffer[..used],
+            &self.config,
+            None,
+            &mut self.last_frame_end,
+        )
+    }
+}
@@ -0,0 +1,168 @@
+//! Splitting of one physical strip into independently written segments.
+
+use smart_leds_trait::{SmartLedsWrite, SmartLedsWriteAsync, RGB8};
+
+use crate::LedAdapterError;
+
+/// Strip of `N` LEDs on top of a smart LED writer, split into `S` segments,
+/// e.g. status LEDs, a ring and a bar on the same data line.
+///
+/// Every segment is written on its own through [SmartLedsWrite], which only
+/// updates its part of the frame in memory, with indices starting at 0 at
+/// its first LED. [SegmentedStrip::present] then sends the combined frame.
+///
+/// ```rust,ignore
+/// // 2 status LEDs, a ring of 16 and a bar of 12
+/// let mut strip = SegmentedStrip::<_, 30, 3>::new(led, [(0, 2), (2, 16), (18, 12)]);
+/// let [_status, mut ring, mut bar] = strip.segments();
+/// ring.write(ring_colors)?;
+/// bar.write(bar_colors)?;
+/// strip.present()?;
+/// ```
+pub struct SegmentedStrip<W, const N: usize, const S: usize> {
+    writer: W,
+    pixels: [RGB8; N],
+    // Offset and length of every segment
+    segments: [(usize, usize); S],
+}
+
+impl<W, const N: usize, const S: usize> SegmentedStrip<W, N, S> {
+    /// Create a new strip with all LEDs turned off, split into segments given
+    /// as `(offset, length)` on the strip.
+    ///
+    /// # Panics
+    ///
+    /// If a segment extends beyond the `N` LEDs of the strip or two segments
+    /// overlap.
+    pub fn new(writer: W, segments: [(usize, usize); S]) -> Self {
+        for (i, &(offset, len)) in segments.iter().enumerate() {
+            assert!(
+                offset.checked_add(len).is_some_and(|end| end <= N),
+                "segment extends beyond the strip"
+            );
+            assert!(
+                segments[..i]
+                    .iter()
+                    .all(|&(other, other_len)| offset + len <= other || other + other_len <= offset),
+                "segments overlap"
+            );
+        }
+
+        Self {
+            writer,
+            pixels: [RGB8::default(); N],
+            segments,
+        }
+    }
+
+    /// One segment, to be written independently of the others.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn segment(&mut self, index: usize) -> Segment<'_> {
+        let (offset, len) = self.segments[index];
+        Segment {
+            pixels: &mut self.pixels[offset..offset + len],
+        }
+    }
+
+    /// All segments at once, in the order they were given.
+    pub fn segments(&mut self) -> [Segment<'_>; S] {
+        let pixels = self.pixels.as_mut_ptr();
+        core::array::from_fn(|i| {
+            let (offset, len) = self.segments[i];
+            // SAFETY: `new` checked that every segment lies within the `N`
+            // pixels and that no two segments overlap, and the segments can't
+            // be changed afterwards. The slices are therefore disjoint, and
+            // they borrow `self` mutably for their whole lifetime.
+            let pixels = unsafe { core::slice::from_raw_parts_mut(pixels.add(offset), len) };
+            Segment { pixels }
+        })
+    }
+
+    /// The colors of the whole strip.
+    pub fn pixels(&self) -> &[RGB8; N] {
+        &self.pixels
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, const N: usize, const S: usize> SegmentedStrip<W, N, S>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    /// Encode and transmit the combined frame of all segments.
+    pub fn present(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied())
+    }
+}
+
+impl<W, const N: usize, const S: usize> SegmentedStrip<W, N, S>
+where
+    W: SmartLedsWriteAsync<Color = RGB8>,
+{
+    /// Encode and transmit the combined frame of all segments.
+    pub async fn present_async(&mut self) -> Result<(), W::Error> {
+        self.writer.write(self.pixels.iter().copied()).await
+    }
+}
+
+/// One segment of a [SegmentedStrip].
+pub struct Segment<'a> {
+    pixels: &'a mut [RGB8],
+}
+
+impl Segment<'_> {
+    /// Number of LEDs of the segment.
+    pub fn len(&self) -> usize {
+        self.pixels.len()
+    }
+
+    /// Whether the segment has no LEDs.
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// The colors of the segment.
+    pub fn pixels(&self) -> &[RGB8] {
+        self.pixels
+    }
+
+    /// Mutable access to the colors of the segment.
+    pub fn pixels_mut(&mut self) -> &mut [RGB8] {
+        self.pixels
+    }
+}
+
+impl SmartLedsWrite for Segment<'_> {
+    type Error = LedAdapterError;
+    type Color = RGB8;
+
+    /// Update the colors of the segment from its first LED on, leaving the
+    /// LEDs after the last item unchanged. The frame is only sent by
+    /// [SegmentedStrip::present].
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let capacity = self.pixels.len();
+        for (index, item) in iterator.into_iter().enumerate() {
+            *self
+                .pixels
+                .get_mut(index)
+                .ok_or(LedAdapterError::BufferSizeExceeded {
+                    needed: index + 1,
+                    capacity,
+                    encoded: index,
+                })? = item.into();
+        }
+
+        Ok(())
+    }
+}
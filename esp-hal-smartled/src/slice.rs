@@ -0,0 +1,347 @@
+//! Adapter whose RMT buffer is sized at runtime instead of through a const
+//! generic.
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{Channel, RawChannelAccess, TxChannelCreator, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+use crate::{
+    check_len, configure_channel, encode_frame,
+    encoder::{BitPulses, LedEncoder, Rgb8Encoder},
+    led_pulses_for_clock, reset_pulse_for_clock, AdapterConfig, ChannelState, ColorTemperature,
+    LedAdapterError, PowerScale,
+};
+
+/// Adapter taking an RMT channel and a specific pin and providing RGB LED
+/// interaction functionality using the `smart-leds` crate, with an RMT buffer
+/// of type `B` whose size is only known at runtime.
+///
+/// The colors are converted by the [LedEncoder] `E`, see
+/// [SmartLedsAdapterSlice::from_buffer_with_encoder] to drive other chips.
+pub struct SmartLedsAdapterSlice<TX, B, E = Rgb8Encoder>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    B: AsRef<[u32]> + AsMut<[u32]>,
+    E: LedEncoder,
+{
+    channel: ChannelState<TX>,
+    rmt_buffer: B,
+    encoder: E,
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+}
+
+/// [SmartLedsAdapterSlice] borrowing its RMT buffer, e.g. from a `static`.
+pub type SmartLedsAdapterRef<'buf, TX, E = Rgb8Encoder> =
+    SmartLedsAdapterSlice<TX, &'buf mut [u32], E>;
+
+/// [SmartLedsAdapterSlice] owning a heap-allocated RMT buffer.
+#[cfg(feature = "alloc")]
+pub type SmartLedsAdapterBoxed<TX, E = Rgb8Encoder> = SmartLedsAdapterSlice<TX, Box<[u32]>, E>;
+
+#[cfg(feature = "alloc")]
+impl<'d, TX> SmartLedsAdapterSlice<TX, Box<[u32]>>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// allocating an RMT buffer for `num_leds` LEDs on the heap.
+    pub fn new_boxed<C, O>(channel: C, pin: O, num_leds: usize) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_boxed_with_config(channel, pin, num_leds, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, allocating an RMT buffer for `num_leds`
+    /// LEDs on the heap.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterSlice::try_new_boxed_with_config].
+    pub fn new_boxed_with_config<C, O>(
+        channel: C,
+        pin: O,
+        num_leds: usize,
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_boxed_with_config(channel, pin, num_leds, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, allocating an RMT buffer for `num_leds`
+    /// LEDs on the heap, returning an error instead of panicking if the
+    /// channel can't be configured.
+    pub fn try_new_boxed_with_config<C, O>(
+        channel: C,
+        pin: O,
+        num_leds: usize,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        let rmt_buffer = vec![0; crate::buffer_size(num_leds)].into_boxed_slice();
+        Self::from_buffer(channel, pin, rmt_buffer, config)
+    }
+}
+
+impl<'d, 'buf, TX> SmartLedsAdapterSlice<TX, &'buf mut [u32]>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// borrowing the RMT buffer.
+    ///
+    /// The buffer has to be sized with [buffer_size](crate::buffer_size),
+    /// otherwise [LedAdapterError::InvalidBufferSize] is returned. As the
+    /// size is checked at runtime, the number of LEDs doesn't need to be
+    /// known at compile time. Borrowing the buffer, e.g. from a `static`,
+    /// avoids moving large buffers around on the stack and allows placing it
+    /// in a specific memory region, see
+    /// [SmartLedsAdapterSlice::from_buffer] for external RAM.
+    pub fn new_with_buffer<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: &'buf mut [u32],
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_buffer_and_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, borrowing the RMT buffer.
+    pub fn new_with_buffer_and_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: &'buf mut [u32],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::from_buffer(channel, pin, rmt_buffer, config)
+    }
+}
+
+impl<'d, TX, B> SmartLedsAdapterSlice<TX, B>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    B: AsRef<[u32]> + AsMut<[u32]>,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, taking any kind of RMT buffer.
+    ///
+    /// The buffer has to be sized with [buffer_size](crate::buffer_size),
+    /// otherwise [LedAdapterError::InvalidBufferSize] is returned.
+    ///
+    /// # External RAM
+    ///
+    /// The buffer can be placed in PSRAM, e.g. with `esp-alloc` or a `static`
+    /// in the `.ext_ram.bss` section, to drive very large installations. The
+    /// RMT peripheral doesn't use DMA, the pulses are copied from the buffer
+    /// into the RAM of the channel by the CPU, so there are no alignment
+    /// requirements and no cache has to be flushed after encoding. Reading
+    /// from PSRAM is slower though, use [AdapterConfig::with_memsize] if
+    /// the channel runs out of data while it is refilled.
+    pub fn from_buffer<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: B,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::from_buffer_with_encoder(channel, pin, rmt_buffer, Rgb8Encoder, config)
+    }
+}
+
+impl<'d, TX, B, E> SmartLedsAdapterSlice<TX, B, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    B: AsRef<[u32]> + AsMut<[u32]>,
+    E: LedEncoder,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, taking any kind of RMT buffer and
+    /// converting the colors with the encoder.
+    ///
+    /// The buffer has to be sized with
+    /// [buffer_size_custom](crate::buffer_size_custom) for the encoder,
+    /// otherwise [LedAdapterError::InvalidBufferSize] is returned. See
+    /// [SmartLedsAdapterSlice::from_buffer] for buffers in external RAM.
+    pub fn from_buffer_with_encoder<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: B,
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const { assert!(E::PULSES_PER_LED > 0, "PULSES_PER_LED must not be 0") };
+
+        let len = rmt_buffer.as_ref().len();
+        if len == 0 || (len - 1) % E::PULSES_PER_LED != 0 {
+            return Err(LedAdapterError::InvalidBufferSize { len });
+        }
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            channel: ChannelState::Ready(channel),
+            rmt_buffer,
+            encoder,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            last_frame_end: None,
+            power: PowerScale::NONE,
+        })
+    }
+
+    /// The encoder of the adapter.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Mutable access to the encoder, e.g. to change its settings.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Number of LEDs the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.rmt_buffer.as_ref().len().saturating_sub(1) / E::PULSES_PER_LED
+    }
+
+    /// Number of LEDs the buffer can hold, same as
+    /// [SmartLedsAdapterSlice::capacity].
+    pub fn capacity_leds(&self) -> usize {
+        self.capacity()
+    }
+
+    /// Length of the RMT buffer in pulse codes.
+    pub fn buffer_len(&self) -> usize {
+        self.rmt_buffer.as_ref().len()
+    }
+
+    /// Check whether a frame of `len` LEDs fits into the buffer, see
+    /// [SmartLedsAdapter::check_len](crate::SmartLedsAdapter::check_len).
+    pub fn check_len(&self, len: usize) -> Result<(), LedAdapterError> {
+        check_len(len, self.capacity())
+    }
+
+    /// Set all LEDs the buffer can hold to `color`.
+    pub fn fill(&mut self, color: E::Color) -> Result<(), LedAdapterError>
+    where
+        E::Color: Clone,
+    {
+        self.write(core::iter::repeat_n(color, self.capacity()))
+    }
+
+    /// Turn off all LEDs the buffer can hold.
+    pub fn clear(&mut self) -> Result<(), LedAdapterError>
+    where
+        E::Color: Clone + Default,
+    {
+        self.fill(E::Color::default())
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+
+    /// Scale all colors written from now on by `brightness / 256`, see
+    /// [AdapterConfig::with_brightness].
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.config = self.config.with_brightness(brightness);
+    }
+
+    /// The brightness all colors are scaled with.
+    pub fn brightness(&self) -> u8 {
+        self.config.brightness()
+    }
+
+    /// Scale the red, green and blue channels of all colors written from now
+    /// on, see [AdapterConfig::with_correction].
+    pub fn set_correction(&mut self, correction: RGB8) {
+        self.config = self.config.with_correction(correction);
+    }
+
+    /// Tint all colors written from now on to the color of a light source,
+    /// see [AdapterConfig::with_color_temperature].
+    pub fn set_color_temperature(&mut self, color_temperature: ColorTemperature) {
+        self.config = self.config.with_color_temperature(color_temperature);
+    }
+}
+
+impl<TX, B, E> SmartLedsWrite for SmartLedsAdapterSlice<TX, B, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    B: AsRef<[u32]> + AsMut<[u32]>,
+    E: LedEncoder,
+{
+    type Error = LedAdapterError;
+    type Color = E::Color;
+
+    /// Convert all items of the iterator to the RMT format and
+    /// add them to internal buffer, then start a singular RMT operation
+    /// based on that buffer.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let led_count = encode_frame(
+            &mut self.encoder,
+            self.rmt_buffer.as_mut(),
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )?;
+
+        let used = led_count * E::PULSES_PER_LED + 1;
+        self.channel.send_frame(
+            &self.rmt_buffer.as_ref()[..used],
+            &self.config,
+            None,
+            &mut self.last_frame_end,
+        )
+    }
+}
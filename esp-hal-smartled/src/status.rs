@@ -0,0 +1,136 @@
+//! Status indicator patterns for a single LED, e.g. the onboard one of a
+//! development board.
+
+use esp_hal::time::Instant;
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+// Timing of the blinks of an error code, in milliseconds.
+const CODE_ON_MS: u64 = 200;
+const CODE_OFF_MS: u64 = 300;
+const CODE_PAUSE_MS: u64 = 1500;
+
+/// Pattern shown by a [StatusLed].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusPattern {
+    /// The LED is off.
+    Off,
+    /// The LED is on with one color.
+    Solid(RGB8),
+    /// The LED blinks with one color.
+    Blink {
+        /// Color while the LED is on.
+        color: RGB8,
+        /// Time the LED is on in milliseconds.
+        on_ms: u32,
+        /// Time the LED is off in milliseconds.
+        off_ms: u32,
+    },
+    /// The LED blinks `code` times, pauses and starts over, to show an
+    /// error code.
+    ErrorCode {
+        /// Color while the LED is on.
+        color: RGB8,
+        /// Number of blinks.
+        code: u8,
+    },
+}
+
+impl StatusPattern {
+    /// Color of the pattern `elapsed_ms` after it was started.
+    pub fn color_at(&self, elapsed_ms: u64) -> RGB8 {
+        let off = RGB8::default();
+        match *self {
+            StatusPattern::Off => off,
+            StatusPattern::Solid(color) => color,
+            StatusPattern::Blink {
+                color,
+                on_ms,
+                off_ms,
+            } => {
+                let period = on_ms as u64 + off_ms as u64;
+                if period == 0 || elapsed_ms % period < on_ms as u64 {
+                    color
+                } else {
+                    off
+                }
+            }
+            StatusPattern::ErrorCode { color, code } => {
+                let blink = CODE_ON_MS + CODE_OFF_MS;
+                let period = code as u64 * blink + CODE_PAUSE_MS;
+                let phase = elapsed_ms % period;
+                if phase < code as u64 * blink && phase % blink < CODE_ON_MS {
+                    color
+                } else {
+                    off
+                }
+            }
+        }
+    }
+}
+
+/// Status indicator on top of a smart LED writer driving a single LED,
+/// showing a [StatusPattern] without blocking.
+///
+/// [StatusLed::tick] has to be called regularly, e.g. from the main loop,
+/// and only writes to the LED when its color changes.
+///
+/// ```rust,ignore
+/// let mut status = StatusLed::new(led);
+/// status.set_pattern(StatusPattern::ErrorCode { color: RED, code: 3 });
+/// loop {
+///     status.tick(Instant::now())?;
+///     // ...
+/// }
+/// ```
+pub struct StatusLed<W> {
+    writer: W,
+    pattern: StatusPattern,
+    started: Option<Instant>,
+    shown: Option<RGB8>,
+}
+
+impl<W> StatusLed<W>
+where
+    W: SmartLedsWrite<Color = RGB8>,
+{
+    /// Create a new status indicator, which is off.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pattern: StatusPattern::Off,
+            started: None,
+            shown: None,
+        }
+    }
+
+    /// Show another pattern, starting from its beginning on the next
+    /// [StatusLed::tick].
+    pub fn set_pattern(&mut self, pattern: StatusPattern) {
+        self.pattern = pattern;
+        self.started = None;
+    }
+
+    /// The pattern shown.
+    pub fn pattern(&self) -> StatusPattern {
+        self.pattern
+    }
+
+    /// Update the LED to the state of the pattern at `now`.
+    pub fn tick(&mut self, now: Instant) -> Result<(), W::Error> {
+        let started = *self.started.get_or_insert(now);
+        let color = self.pattern.color_at((now - started).as_millis());
+        if self.shown == Some(color) {
+            return Ok(());
+        }
+
+        self.writer.write([color])?;
+        self.shown = Some(color);
+
+        Ok(())
+    }
+
+    /// Release the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
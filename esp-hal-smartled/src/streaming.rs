@@ -0,0 +1,270 @@
+//! Adapter encoding the LEDs while they are transmitted, so its memory use
+//! doesn't depend on the length of the strip.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{Channel, RawChannelAccess, TxChannel, TxChannelCreator, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::SmartLedsWrite;
+
+use crate::{
+    configure_channel,
+    encoder::{BitPulses, LedEncoder, Rgb8Encoder},
+    led_pulses_for_clock, reset_pulse_for_clock, wait_for_frame_gap, AdapterConfig, ChannelState,
+    LedAdapterError, PowerScale, RMT_BLOCK_SIZE,
+};
+
+// The RAM of a single RMT channel, which holds one LED followed by the end
+// delimiter for all encoders the adapter accepts.
+const CHUNK_SIZE: usize = RMT_BLOCK_SIZE;
+
+fn encode_chunk<E: LedEncoder>(
+    chunk: &mut [u32; CHUNK_SIZE],
+    encoder: &mut E,
+    color: E::Color,
+    pulses: &BitPulses,
+    config: &AdapterConfig,
+    delimiter: u32,
+) {
+    // The chunk is sized to always fit one LED and the delimiter
+    let (led, rest) = chunk.split_at_mut(E::PULSES_PER_LED);
+    encoder.encode(color, pulses, config, led);
+    rest[0] = delimiter;
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing RGB LED
+/// interaction functionality using the `smart-leds` crate, for strips of any
+/// length.
+///
+/// Instead of encoding the whole frame up front, every LED is sent in its own
+/// RMT transmission while the next one is encoded into a second buffer. This
+/// keeps the memory use constant, at the cost of a short gap on the wire
+/// between LEDs, which the LEDs tolerate as long as it stays below their reset
+/// time. The RMT driver doesn't expose its refill mechanism, so LEDs can't be
+/// encoded into the running transmission itself.
+///
+/// The LEDs are sent before the whole frame is known, so the brightness
+/// [AdapterConfig::with_power_limit] sets for a frame is only applied to the
+/// next one.
+///
+/// The colors are converted by the [LedEncoder] `E`, see
+/// [SmartLedsAdapterStreaming::try_new_with_encoder] to drive other chips.
+pub struct SmartLedsAdapterStreaming<TX, E = Rgb8Encoder>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    channel: ChannelState<TX>,
+    rmt_buffers: [[u32; CHUNK_SIZE]; 2],
+    encoder: E,
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+    last_frame_end: Option<Instant>,
+}
+
+impl<'d, TX> SmartLedsAdapterStreaming<TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel.
+    pub fn new<C, O>(channel: C, pin: O) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channel, pin, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterStreaming::try_new_with_config].
+    pub fn new_with_config<C, O>(channel: C, pin: O, config: AdapterConfig) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channel, pin, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, returning an error instead of panicking
+    /// if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_encoder(channel, pin, Rgb8Encoder, config)
+    }
+}
+
+impl<'d, TX, E> SmartLedsAdapterStreaming<TX, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the encoder and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterStreaming::try_new_with_encoder].
+    pub fn new_with_encoder<C, O>(channel: C, pin: O, encoder: E, config: AdapterConfig) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_encoder(channel, pin, encoder, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// the encoder and the given configuration, returning an error instead of
+    /// panicking if the channel can't be configured.
+    ///
+    /// The pulses of one LED and the end delimiter have to fit into one block
+    /// of RMT RAM, which is checked at compile time.
+    pub fn try_new_with_encoder<C, O>(
+        channel: C,
+        pin: O,
+        encoder: E,
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(
+                E::PULSES_PER_LED > 0 && E::PULSES_PER_LED < CHUNK_SIZE,
+                "one LED and the end delimiter must fit into one block of RMT RAM"
+            )
+        };
+
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            channel: ChannelState::Ready(channel),
+            rmt_buffers: [[0; CHUNK_SIZE]; 2],
+            encoder,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            power: PowerScale::NONE,
+            last_frame_end: None,
+        })
+    }
+
+    /// The encoder of the adapter.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Mutable access to the encoder, e.g. to change its settings.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+}
+
+impl<TX, E> SmartLedsWrite for SmartLedsAdapterStreaming<TX, E>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+    E: LedEncoder,
+{
+    type Error = LedAdapterError;
+    type Color = E::Color;
+
+    /// Send the items of the iterator one LED at a time, encoding each LED
+    /// while the previous one is transmitted.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let (pulses, reset_pulse) = (self.pulses, self.reset_pulse);
+        let config = self.power.apply(&self.config);
+        let mut colors = iterator.into_iter().map(Into::into).peekable();
+
+        let Some(first) = colors.next() else {
+            return Ok(());
+        };
+
+        let [first_buffer, second_buffer] = &mut self.rmt_buffers;
+        let (mut current, mut next) = (first_buffer, second_buffer);
+        let encoder = &mut self.encoder;
+        encoder.start_frame();
+        // Only the last LED holds the line low for the reset time
+        let delimiter = |last: bool| if last { reset_pulse } else { 0 };
+        let last = colors.peek().is_none();
+        let (mut led_count, mut load) = (1, encoder.load(&first, &self.config));
+        encode_chunk(current, encoder, first, &pulses, &config, delimiter(last));
+
+        wait_for_frame_gap(self.last_frame_end, config.frame_gap_ns());
+        let mut channel = self.channel.take()?;
+
+        let result = loop {
+            let transaction = match channel.transmit(&current[..=E::PULSES_PER_LED]) {
+                Ok(transaction) => transaction,
+                Err(e) => break Err(LedAdapterError::TransmissionError(e)),
+            };
+
+            let more = match colors.next() {
+                Some(color) => {
+                    let last = colors.peek().is_none();
+                    led_count += 1;
+                    load = load.saturating_add(encoder.load(&color, &self.config));
+                    encode_chunk(next, encoder, color, &pulses, &config, delimiter(last));
+                    true
+                }
+                None => false,
+            };
+
+            match transaction.wait() {
+                Ok(chan) => channel = chan,
+                Err((e, chan)) => {
+                    self.channel = ChannelState::Ready(chan);
+                    break Err(LedAdapterError::TransmissionError(e));
+                }
+            }
+
+            if !more {
+                self.channel = ChannelState::Ready(channel);
+                break Ok(());
+            }
+
+            core::mem::swap(&mut current, &mut next);
+        };
+        self.last_frame_end = Some(Instant::now());
+        self.power = PowerScale::of(&self.config, led_count, load);
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+}
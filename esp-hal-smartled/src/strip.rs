@@ -0,0 +1,86 @@
+//! Adapter whose RMT buffer is derived from the number of LEDs.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{RawChannelAccess, TxChannelCreator, TxChannelInternal},
+    Blocking,
+};
+
+use crate::{AdapterConfig, SmartLedsAdapterSlice, RMT_RAM_ONE_LED};
+
+/// RMT buffer for `N_LEDS` LEDs and the end delimiter.
+#[repr(C)]
+pub struct LedStripBuffer<const N_LEDS: usize> {
+    leds: [[u32; RMT_RAM_ONE_LED]; N_LEDS],
+    delimiter: u32,
+}
+
+impl<const N_LEDS: usize> LedStripBuffer<N_LEDS> {
+    /// Number of pulse codes in the buffer.
+    const LEN: usize = N_LEDS * RMT_RAM_ONE_LED + 1;
+
+    /// Create an empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            leds: [[0; RMT_RAM_ONE_LED]; N_LEDS],
+            delimiter: 0,
+        }
+    }
+}
+
+impl<const N_LEDS: usize> Default for LedStripBuffer<N_LEDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N_LEDS: usize> AsRef<[u32]> for LedStripBuffer<N_LEDS> {
+    fn as_ref(&self) -> &[u32] {
+        // SAFETY: The struct is `repr(C)` and only consists of `u32`s, so the
+        // delimiter directly follows the LEDs without padding.
+        unsafe { core::slice::from_raw_parts((self as *const Self).cast(), Self::LEN) }
+    }
+}
+
+impl<const N_LEDS: usize> AsMut<[u32]> for LedStripBuffer<N_LEDS> {
+    fn as_mut(&mut self) -> &mut [u32] {
+        // SAFETY: See `as_ref`.
+        unsafe { core::slice::from_raw_parts_mut((self as *mut Self).cast(), Self::LEN) }
+    }
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing RGB LED
+/// interaction functionality using the `smart-leds` crate for a strip of
+/// `N_LEDS` LEDs.
+///
+/// The RMT buffer is sized from the number of LEDs, so it can't be mismatched.
+pub type SmartLedStrip<TX, const N_LEDS: usize> = SmartLedsAdapterSlice<TX, LedStripBuffer<N_LEDS>>;
+
+impl<'d, TX, const N_LEDS: usize> SmartLedsAdapterSlice<TX, LedStripBuffer<N_LEDS>>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel.
+    pub fn new<C, O>(channel: C, pin: O) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channel, pin, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [SmartLedsAdapterSlice::from_buffer].
+    pub fn new_with_config<C, O>(channel: C, pin: O, config: AdapterConfig) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::from_buffer(channel, pin, LedStripBuffer::new(), config).unwrap()
+    }
+}
@@ -0,0 +1,262 @@
+//! Support for TM1814 RGBW LEDs, which use an inverted line and expect a
+//! current setting before the color data of every frame.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    rmt::{Channel, RawChannelAccess, TxChannelCreator, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::{SmartLedsWrite, White, RGBW};
+
+use crate::{
+    configure_channel, correct_channel, encode_frame,
+    encoder::{BitPulses, LedEncoder},
+    led_pulses_for_clock, reset_pulse_for_clock, AdapterConfig, ChannelState, LedAdapterError,
+    LedTiming, PowerScale, Timing,
+};
+
+// Required RMT RAM to drive one LED.
+const RMT_RAM_ONE_TM1814_LED: usize = Tm1814Encoder::PULSES_PER_LED;
+
+// Required RMT RAM for the current setting, which is sent once and then
+// repeated inverted.
+const RMT_RAM_TM1814_PREAMBLE: usize = 2 * RMT_RAM_ONE_TM1814_LED;
+
+// Maximum value of the current setting of a channel.
+const MAX_CURRENT: u8 = 63;
+
+/// Function to calculate the required RMT buffer size for a given number of
+/// TM1814 LEDs when using the blocking API.
+pub const fn buffer_size_tm1814(num_leds: usize) -> usize {
+    // 1 additional pulse for the end delimiter
+    RMT_RAM_TM1814_PREAMBLE + num_leds * RMT_RAM_ONE_TM1814_LED + 1
+}
+
+/// Macro to allocate a buffer sized for a specific number of TM1814 LEDs to be
+/// addressed.
+///
+/// Attempting to use more LEDs that the buffer is configured for will result in
+/// an `LedAdapterError::BufferSizeExceeded` error.
+#[macro_export]
+macro_rules! smart_led_buffer_tm1814 {
+    ( $num_leds: expr ) => {
+        [0u32; $crate::buffer_size_tm1814($num_leds)]
+    };
+}
+
+/// Adapter taking an RMT channel and a specific pin and providing TM1814 LED
+/// interaction functionality using the `smart-leds` crate
+///
+/// The line is inverted, i.e. it idles high, as the LEDs expect.
+pub struct Tm1814Adapter<TX, const BUFFER_SIZE: usize>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    channel: ChannelState<TX>,
+    rmt_buffer: [u32; BUFFER_SIZE],
+    pulses: BitPulses,
+    reset_pulse: u32,
+    config: AdapterConfig,
+    current: RGBW<u8>,
+    last_frame_end: Option<Instant>,
+    // Power limit of the previous frame, applied to the next one
+    power: PowerScale,
+}
+
+impl<'d, TX, const BUFFER_SIZE: usize> Tm1814Adapter<TX, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Create a new adapter object that drives the pin using the RMT channel,
+    /// with the current setting of all channels at its maximum.
+    pub fn new<C, O>(channel: C, pin: O, rmt_buffer: [u32; BUFFER_SIZE]) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::new_with_config(channel, pin, rmt_buffer, AdapterConfig::default())
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, e.g. with
+    /// [AdapterConfig::with_auto_refresh].
+    ///
+    /// The timing and polarity of the configuration are replaced with those
+    /// of the TM1814.
+    ///
+    /// # Panics
+    ///
+    /// If the channel can't be configured, see
+    /// [Tm1814Adapter::try_new_with_config].
+    pub fn new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Self
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        Self::try_new_with_config(channel, pin, rmt_buffer, config).unwrap()
+    }
+
+    /// Create a new adapter object that drives the pin using the RMT channel
+    /// and the given configuration, returning an error instead of panicking
+    /// if the channel can't be configured.
+    pub fn try_new_with_config<C, O>(
+        channel: C,
+        pin: O,
+        rmt_buffer: [u32; BUFFER_SIZE],
+        config: AdapterConfig,
+    ) -> Result<Self, LedAdapterError>
+    where
+        O: PeripheralOutput<'d>,
+        C: TxChannelCreator<'d, Blocking, Raw = TX>,
+    {
+        const {
+            assert!(
+                BUFFER_SIZE % RMT_RAM_ONE_TM1814_LED == 1 && BUFFER_SIZE > RMT_RAM_TM1814_PREAMBLE,
+                "BUFFER_SIZE must be sized with `buffer_size_tm1814`"
+            )
+        };
+
+        let config = config
+            .with_timing(Timing::Custom(LedTiming::TM1814))
+            .with_inverted(true);
+        let tick_hz = config.tick_rate().as_hz();
+        let pulses = led_pulses_for_clock(tick_hz, config.timing(), config.inverted())?;
+
+        let channel = configure_channel(channel, pin, &config)?;
+
+        Ok(Self {
+            channel: ChannelState::Ready(channel),
+            rmt_buffer,
+            pulses,
+            reset_pulse: reset_pulse_for_clock(tick_hz, config.timing(), config.inverted()),
+            config,
+            current: RGBW {
+                r: MAX_CURRENT,
+                g: MAX_CURRENT,
+                b: MAX_CURRENT,
+                a: White(MAX_CURRENT),
+            },
+            last_frame_end: None,
+            power: PowerScale::NONE,
+        })
+    }
+
+    /// Set the current setting of every channel, sent at the start of every
+    /// frame. Values range from 0 (lowest) to 63 (highest), above that they
+    /// are clamped.
+    pub fn with_current(mut self, current: RGBW<u8>) -> Self {
+        self.set_current(current);
+        self
+    }
+
+    /// Set the current setting of every channel, sent at the start of every
+    /// frame. Values range from 0 (lowest) to 63 (highest), above that they
+    /// are clamped.
+    pub fn set_current(&mut self, current: RGBW<u8>) {
+        self.current = RGBW {
+            r: current.r.min(MAX_CURRENT),
+            g: current.g.min(MAX_CURRENT),
+            b: current.b.min(MAX_CURRENT),
+            a: White(current.a.0.min(MAX_CURRENT)),
+        };
+    }
+
+    /// The current setting of every channel.
+    pub fn current(&self) -> RGBW<u8> {
+        self.current
+    }
+
+    /// Give up the adapter and return the configured RMT channel.
+    ///
+    /// See [SmartLedsAdapter::release](crate::SmartLedsAdapter::release).
+    pub fn release(mut self) -> Result<Channel<Blocking, TX>, LedAdapterError> {
+        self.channel.take()
+    }
+}
+
+// The channels of a color in the order they are sent.
+fn wrgb(color: RGBW<u8>) -> [u8; 4] {
+    [color.a.0, color.r, color.g, color.b]
+}
+
+/// Encoder of the colors of TM1814 LEDs, which are sent white first and
+/// ignore the color order of the configuration.
+struct Tm1814Encoder;
+
+impl LedEncoder for Tm1814Encoder {
+    type Color = RGBW<u8>;
+    const PULSES_PER_LED: usize = 4 * 8;
+
+    fn encode(
+        &mut self,
+        color: RGBW<u8>,
+        pulses: &BitPulses,
+        config: &AdapterConfig,
+        out: &mut [u32],
+    ) {
+        for (channel, out) in wrgb(color).into_iter().zip(out.chunks_exact_mut(8)) {
+            pulses.write_bits(correct_channel(channel, config) as u32, out);
+        }
+    }
+
+    fn load(&self, color: &RGBW<u8>, config: &AdapterConfig) -> u32 {
+        wrgb(*color)
+            .into_iter()
+            .map(|channel| correct_channel(channel, config) as u32)
+            .sum()
+    }
+}
+
+impl<TX, const BUFFER_SIZE: usize> SmartLedsWrite for Tm1814Adapter<TX, BUFFER_SIZE>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    type Error = LedAdapterError;
+    type Color = RGBW<u8>;
+
+    /// Convert all RGBW items of the iterator to the RMT format and
+    /// add them to internal buffer after the current setting, then start a
+    /// singular RMT operation based on that buffer.
+    fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Self::Color>,
+    {
+        let (preamble, leds) = self.rmt_buffer.split_at_mut(RMT_RAM_TM1814_PREAMBLE);
+
+        // The current setting, followed by its complement. The buffer always
+        // has room for it, as asserted in `new`.
+        let current = wrgb(self.current);
+        for (channel, out) in current
+            .into_iter()
+            .chain(current.map(|c| !c))
+            .zip(preamble.chunks_exact_mut(8))
+        {
+            self.pulses.write_bits(channel as u32, out);
+        }
+
+        let led_count = encode_frame(
+            &mut Tm1814Encoder,
+            leds,
+            iterator,
+            &self.pulses,
+            &self.config,
+            &mut self.power,
+            self.reset_pulse,
+        )?;
+
+        let used = RMT_RAM_TM1814_PREAMBLE + led_count * RMT_RAM_ONE_TM1814_LED + 1;
+        self.channel.send_frame(
+            &self.rmt_buffer[..used],
+            &self.config,
+            None,
+            &mut self.last_frame_end,
+        )
+    }
+}
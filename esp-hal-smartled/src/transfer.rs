@@ -0,0 +1,138 @@
+//! Writes that return while the frame is still being transmitted.
+
+use esp_hal::{
+    rmt::{Channel, RawChannelAccess, SingleShotTxTransaction, TxChannelInternal},
+    time::Instant,
+    Blocking,
+};
+use smart_leds_trait::RGB8;
+
+#[cfg(feature = "stats")]
+use crate::FrameStats;
+use crate::{rmt_ram_one_led, ChannelState, LedAdapterError, SmartLedsAdapter};
+
+/// A frame being transmitted by a [SmartLedsAdapter], returned by
+/// [SmartLedsAdapter::start_write].
+///
+/// The RMT driver only refills the RAM of the channel while the transfer is
+/// polled, so frames that don't fit into it (see
+/// [AdapterConfig::with_memsize](crate::AdapterConfig::with_memsize)) need
+/// [WriteTransfer::is_done] to be called more often than the channel takes to
+/// send half of its RAM. Dropping the transfer waits for it to finish.
+///
+/// Frames repeated with
+/// [AdapterConfig::with_auto_refresh](crate::AdapterConfig::with_auto_refresh)
+/// are done right away.
+pub struct WriteTransfer<'a, TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    transaction: Option<SingleShotTxTransaction<'a, Channel<Blocking, TX>, u32>>,
+    channel: &'a mut ChannelState<TX>,
+    last_frame_end: &'a mut Option<Instant>,
+    #[cfg(feature = "stats")]
+    stats: (&'a mut FrameStats, Instant, usize),
+}
+
+impl<TX> WriteTransfer<'_, TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Whether the frame has been sent completely.
+    pub fn is_done(&mut self) -> bool {
+        match &mut self.transaction {
+            Some(transaction) => transaction.poll(),
+            None => true,
+        }
+    }
+
+    /// Wait for the frame to be sent completely.
+    pub fn wait(mut self) -> Result<(), LedAdapterError> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<(), LedAdapterError> {
+        let Some(transaction) = self.transaction.take() else {
+            return Ok(());
+        };
+
+        let result = match transaction.wait() {
+            Ok(channel) => {
+                *self.channel = ChannelState::Ready(channel);
+                Ok(())
+            }
+            Err((e, channel)) => {
+                *self.channel = ChannelState::Ready(channel);
+                Err(LedAdapterError::TransmissionError(e))
+            }
+        };
+        *self.last_frame_end = Some(Instant::now());
+        #[cfg(feature = "stats")]
+        {
+            let (stats, start, led_count) = &mut self.stats;
+            stats.record_transmit(*start, *led_count, result.is_ok());
+        }
+
+        #[cfg(any(feature = "defmt", feature = "log"))]
+        if let Err(e) = &result {
+            warn!("LED transmission failed: {:?}", e);
+        }
+
+        result
+    }
+}
+
+impl<TX> Drop for WriteTransfer<'_, TX>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    fn drop(&mut self) {
+        self.finish().ok();
+    }
+}
+
+impl<TX, const BUFFER_SIZE: usize, const BITS_PER_CHANNEL: usize>
+    SmartLedsAdapter<TX, BUFFER_SIZE, BITS_PER_CHANNEL>
+where
+    TX: RawChannelAccess + TxChannelInternal + 'static,
+{
+    /// Encode the colors of the iterator and start transmitting them without
+    /// waiting for the transmission to finish.
+    ///
+    /// This lets the application do other work while the frame is sent, e.g.
+    /// in a superloop. Poll the returned [WriteTransfer] until it is done.
+    /// Apart from that, the frame is sent like with
+    /// [SmartLedsWrite::write](smart_leds_trait::SmartLedsWrite::write).
+    pub fn start_write<T, I>(
+        &mut self,
+        iterator: T,
+    ) -> Result<WriteTransfer<'_, TX>, LedAdapterError>
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<RGB8>,
+    {
+        let led_count = self.encode(iterator)?;
+        let used = led_count * rmt_ram_one_led(BITS_PER_CHANNEL) + 1;
+
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let result = self.channel.start_frame(
+            &self.rmt_buffer[..used],
+            &self.config,
+            &mut self.last_frame_end,
+        );
+        #[cfg(feature = "stats")]
+        if !matches!(result, Ok(Some(_))) {
+            // Nothing to wait for, so the transmission is recorded right away
+            self.stats.record_transmit(start, led_count, result.is_ok());
+        }
+
+        Ok(WriteTransfer {
+            transaction: result?,
+            channel: &mut self.channel,
+            last_frame_end: &mut self.last_frame_end,
+            #[cfg(feature = "stats")]
+            stats: (&mut self.stats, start, led_count),
+        })
+    }
+}